@@ -0,0 +1,20 @@
+#define_import_path every_few_seconds::global_time
+
+// Matches `global_time_uniform` in src/game/materials/global_time.rs.
+// It's the one texel of a 1×1 texture. Each material binds that texture itself,
+// since materials live in different bind groups, and reads it with `load_global_time`.
+struct GlobalTime {
+    seconds: f32,  // whole seconds since startup
+    fraction: f32, // how far into the current second we are
+    _padding: vec2<f32>,
+}
+
+fn load_global_time(texel: vec4<f32>) -> GlobalTime {
+    return GlobalTime(texel.x, texel.y, texel.zw);
+}
+
+// Seconds since `timestamp`, subtracting whole and fractional parts separately
+// so the result stays precise even when both are large.
+fn seconds_since(time: GlobalTime, timestamp: f32) -> f32 {
+    return (time.seconds - floor(timestamp)) + (time.fraction - fract(timestamp));
+}
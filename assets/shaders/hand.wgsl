@@ -1,19 +1,29 @@
 #import bevy_sprite::mesh2d_vertex_output::VertexOutput
-#import bevy_sprite::mesh2d_view_bindings::globals
+#import every_few_seconds::global_time::{load_global_time, seconds_since}
 
-@group(2) @binding(0) var<uniform> data: vec4<f32>;
+@group(2) @binding(0) var<uniform> data: vec4<f32>; // width, height, cycle start seconds, cycle duration seconds
+@group(2) @binding(1) var<uniform> flash_color: vec4<f32>;
+@group(2) @binding(2) var<uniform> flash: vec4<f32>; // flash start seconds, flash duration seconds, unused, unused
+@group(2) @binding(3) var global_time_texture: texture_2d<f32>;
 
 const BLACK = vec4<f32>(0., 0., 0., 1.);
 const WHITE =  vec4<f32>(1., 1., 1., 1.);
 const TRANSPARENT = vec4<f32>(0., 0., 0., 0.);
 const EDGE_SIZE = 0.005;
 const OUTLINE_THICKNESS = 0.01;
+const TAU = 6.28318530718;
 
 @fragment
 fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
     let width = data[0];
-    let height = data[1];
-    let rotation_radians = data[2];
+    let global_time = load_global_time(textureLoad(global_time_texture, vec2<i32>(0, 0), 0));
+    // worked out exactly as ring.wgsl does, so the hand always lines up with the ring's progress indicator
+    let cycle_progress = fract(seconds_since(global_time, data[2]) / max(data[3], 0.001));
+    // starts pointing up and sweeps clockwise, passing each socket as it triggers
+    let rotation_radians = TAU * cycle_progress - 0.75 * TAU;
+
+    let flash_strength = 1. - clamp(seconds_since(global_time, flash[0]) / max(flash[1], 0.001), 0., 1.);
+    let height = data[1] * (1. + flash_strength);
 
     let uv: vec2<f32> = 2.0 * mesh.uv - vec2<f32>(1.0, 1.0);
     let box_translation = vec2<f32>(width, 0.);
@@ -29,11 +39,11 @@ fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
 
     var final_color = mix(TRANSPARENT, WHITE, smooth_outline);
 
-    final_color = mix(BLACK, final_color, smooth_box);
+    final_color = mix(mix(BLACK, flash_color, flash_strength), final_color, smooth_box);
 
     return mix(TRANSPARENT, final_color, smooth_outline);
 }
- 
+
 fn box(location: vec2<f32>, box: vec2<f32>) -> f32 {
     let distance = abs(location) - box;
     return length(max(distance, vec2<f32>(0.))) + min(max(distance.x, distance.y), 0.0);
@@ -51,4 +61,4 @@ fn rotate(p: vec2<f32>, angle: f32) -> vec2<f32> {
 fn rotate_around(origin: vec2<f32>, angle: f32, point: vec2<f32>) -> vec2<f32> {
     let m = mat2x2<f32>(cos(angle), -sin(angle), sin(angle), cos(angle));
     return (m * (point - origin)) + origin;
-}
\ No newline at end of file
+}
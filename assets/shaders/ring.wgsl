@@ -1,7 +1,8 @@
 #import bevy_sprite::mesh2d_vertex_output::VertexOutput
-#import bevy_sprite::mesh2d_view_bindings::globals
+#import every_few_seconds::global_time::{load_global_time, seconds_since}
 
-@group(2) @binding(0) var<uniform> data: vec4<f32>; // radius, thickness, progress, padding
+@group(2) @binding(0) var<uniform> data: vec4<f32>; // radius, thickness, cycle start seconds, cycle duration seconds
+@group(2) @binding(1) var global_time_texture: texture_2d<f32>;
 
 const BLACK = vec4<f32>(0., 0., 0., 1.);
 const WHITE =  vec4<f32>(1., 1., 1., 1.);
@@ -14,7 +15,9 @@ const INDICATOR_THICKNESS = 0.005;
 fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
     let radius = data[0];
     let thickness = data[1];
-    let progress = 1. - data[2]; // between 0 and 1 representin gthe progress around the ring
+    let global_time = load_global_time(textureLoad(global_time_texture, vec2<i32>(0, 0), 0));
+    let cycle_progress = fract(seconds_since(global_time, data[2]) / max(data[3], 0.001));
+    let progress = 1. - cycle_progress; // between 0 and 1 representin gthe progress around the ring
 
     let uv: vec2<f32> = 2.0 * mesh.uv - vec2<f32>(1.0, 1.0);
 
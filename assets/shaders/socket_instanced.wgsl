@@ -0,0 +1,57 @@
+#import bevy_sprite::mesh2d_view_bindings::view
+#import every_few_seconds::global_time::load_global_time
+#import every_few_seconds::socket_shapes::socket_color
+
+// Many sockets in one draw. Each instance is one socket, laid out as `SocketInstance`
+// in src/game/materials/socket_instances.rs, and the quad is made up from the vertex index.
+
+@group(1) @binding(0) var global_time_texture: texture_2d<f32>;
+
+struct Instance {
+    @location(0) position_size: vec4<f32>, // world x, world y, world z, size
+    @location(1) inserted_color: vec4<f32>,
+    @location(2) bevel_color: vec4<f32>,
+    @location(3) highlight_color: vec4<f32>,
+    @location(4) data: vec4<f32>, // same as the socket material's data
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) @interpolate(flat) inserted_color: vec4<f32>,
+    @location(2) @interpolate(flat) bevel_color: vec4<f32>,
+    @location(3) @interpolate(flat) highlight_color: vec4<f32>,
+    @location(4) @interpolate(flat) data: vec4<f32>,
+};
+
+@vertex
+fn vertex(@builtin(vertex_index) vertex_index: u32, instance: Instance) -> VertexOutput {
+    // two triangles covering the quad, from -1 to 1
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(-1., -1.),
+        vec2<f32>(1., -1.),
+        vec2<f32>(1., 1.),
+        vec2<f32>(-1., -1.),
+        vec2<f32>(1., 1.),
+        vec2<f32>(-1., 1.),
+    );
+    let corner = corners[vertex_index];
+
+    let world_position = instance.position_size.xyz + vec3<f32>(corner * instance.position_size.w * 0.5, 0.);
+
+    var out: VertexOutput;
+    out.clip_position = view.clip_from_world * vec4<f32>(world_position, 1.);
+    // matches a `Rectangle` mesh, which has v = 0 along the top
+    out.uv = vec2<f32>(corner.x * 0.5 + 0.5, 0.5 - corner.y * 0.5);
+    out.inserted_color = instance.inserted_color;
+    out.bevel_color = instance.bevel_color;
+    out.highlight_color = instance.highlight_color;
+    out.data = instance.data;
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let global_time = load_global_time(textureLoad(global_time_texture, vec2<i32>(0, 0), 0));
+    return socket_color(in.uv, in.inserted_color, in.bevel_color, in.highlight_color, in.data, global_time);
+}
@@ -6,9 +6,13 @@ use bevy::{dev_tools::states::log_transitions, prelude::*};
 use bevy_inspector_egui::quick::WorldInspectorPlugin;
 use bevy_mod_picking::debug::DebugPickingMode;
 
+mod console;
+
 pub(super) fn plugin(app: &mut App) {
     // Print state transitions in dev builds
     app.add_systems(Update, log_transitions::<Screen>);
+
+    app.add_plugins(console::plugin);
     // #[cfg(feature = "dev_native")]
     // app.add_plugins(WorldInspectorPlugin::new());
     // app.insert_resource(DebugPickingMode::Normal);
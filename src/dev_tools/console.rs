@@ -0,0 +1,579 @@
+//! An in-game command console for trying things out, opened with the backquote key.
+//!
+//! Type `help` for the commands. Up and Down go through earlier commands, Tab completes the word
+//! being typed.
+
+use std::collections::VecDeque;
+
+use bevy::{
+    color::palettes::{
+        css::WHITE,
+        tailwind::{GRAY_400, GRAY_900},
+    },
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState, InputSystem,
+    },
+    prelude::*,
+    ui::Val::*,
+};
+use bevy_mod_picking::picking_core::Pickable;
+use num_bigint::BigUint;
+
+use crate::{
+    game::{
+        achievements::Achievements,
+        income_estimate::{estimate_income_per_cycle, RingLayout},
+        materials::materials::{RingMaterial, SocketMaterial},
+        number_format::format_number,
+        settings::Settings,
+        spawn::level::{
+            map_socket_color, map_socket_color_trigger_duration, map_socket_highlight_color,
+            spawn_ring, GameplayMeshes, Ring, RingIndex, Socket, SocketColor, NEW_RING_SOCKETS,
+            RING_RADIUS, RING_THICKNESS,
+        },
+    },
+    screen::{
+        playing::{Currency, RunStats},
+        InRun,
+    },
+    ui::shop::{
+        AddColorUpgrade, AddRingUpgrade, AddSocketUpgrade, EnhanceColorUpgrade, Purchase,
+        ShopItem, Upgrade, UpgradeHistory, UpgradeKind,
+    },
+};
+
+/// How many lines of output stay on screen.
+const CONSOLE_OUTPUT_LINES: usize = 12;
+/// How many lines of output are kept at all.
+const MAX_CONSOLE_OUTPUT: usize = 200;
+
+const COMMANDS: [&str; 8] = [
+    "give", "unlock", "ring", "socket", "timescale", "skip", "help", "clear",
+];
+const UPGRADES: [&str; 5] = ["socket", "ring", "color", "enhance", "win"];
+const COLORS: [&str; 5] = ["blue", "red", "green", "orange", "pink"];
+
+const HELP: [&str; 8] = [
+    "give <amount>            add money",
+    "unlock socket <level> | ring <level> | color <color> | enhance <color> <tier> | win",
+    "ring add                 add a ring",
+    "socket fill <ring> <color>   fill every socket of a ring (rings count from 1)",
+    "timescale <x>            speed time up or slow it down, 1 is normal",
+    "skip cycles <n>          earn n cycles' worth of income at once",
+    "clear                    clear the output",
+    "help                     show this",
+];
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Console>();
+
+    // before anything in `Update` reads the keys the console swallows
+    app.add_systems(PreUpdate, read_console_keys.after(InputSystem));
+    app.add_systems(Update, sync_console_panel.run_if(resource_changed::<Console>));
+
+    app.observe(run_console_command);
+}
+
+#[derive(Resource, Default)]
+struct Console {
+    open: bool,
+    input: String,
+    output: VecDeque<String>,
+    history: Vec<String>,
+    /// Which earlier command Up and Down have got to, if any.
+    history_cursor: Option<usize>,
+}
+
+impl Console {
+    fn print(&mut self, line: impl Into<String>) {
+        if self.output.len() == MAX_CONSOLE_OUTPUT {
+            self.output.pop_front();
+        }
+
+        self.output.push_back(line.into());
+    }
+}
+
+/// Trigger this event to run a line typed into the console.
+#[derive(Event)]
+struct RunConsoleCommand(String);
+
+#[derive(Component)]
+struct ConsolePanel;
+
+#[derive(Component)]
+struct ConsoleText;
+
+fn read_console_keys(
+    mut commands: Commands,
+    mut console: ResMut<Console>,
+    mut keyboard_input: EventReader<KeyboardInput>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+) {
+    let was_open = console.open;
+
+    for event in keyboard_input.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        if event.key_code == KeyCode::Backquote {
+            console.open = !console.open;
+            continue;
+        }
+
+        if !console.open {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Character(characters) => console.input.push_str(characters),
+            Key::Space => console.input.push(' '),
+            Key::Backspace => {
+                console.input.pop();
+            }
+            Key::Escape => console.open = false,
+            Key::Tab => autocomplete(&mut console),
+            Key::ArrowUp => browse_history(&mut console, true),
+            Key::ArrowDown => browse_history(&mut console, false),
+            Key::Enter => {
+                let line = std::mem::take(&mut console.input);
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+
+                if console.history.last().map(String::as_str) != Some(line) {
+                    console.history.push(line.to_string());
+                }
+                console.history_cursor = None;
+                console.print(format!("> {}", line));
+
+                commands.trigger(RunConsoleCommand(line.to_string()));
+            }
+            _ => {}
+        }
+    }
+
+    // whatever's typed into the console shouldn't also play the game, including the key closing it
+    if was_open || console.open {
+        keys.reset_all();
+    }
+}
+
+fn browse_history(console: &mut Console, older: bool) {
+    if console.history.is_empty() {
+        return;
+    }
+
+    let newest = console.history.len() - 1;
+    console.history_cursor = match (console.history_cursor, older) {
+        (None, true) => Some(newest),
+        (None, false) => None,
+        (Some(cursor), true) => Some(cursor.saturating_sub(1)),
+        (Some(cursor), false) if cursor < newest => Some(cursor + 1),
+        (Some(_), false) => None,
+    };
+
+    console.input = console
+        .history_cursor
+        .map_or_else(String::new, |cursor| console.history[cursor].clone());
+}
+
+/// What could come next after `words`.
+fn completions(words: &[&str]) -> &'static [&'static str] {
+    match words {
+        [] => &COMMANDS,
+        ["ring"] => &["add"],
+        ["socket"] => &["fill"],
+        ["skip"] => &["cycles"],
+        ["unlock"] => &UPGRADES,
+        ["unlock", "color" | "enhance"] | ["socket", "fill", _] => &COLORS,
+        _ => &[],
+    }
+}
+
+/// Finishes the word being typed, or as much of it as all the options agree on.
+fn autocomplete(console: &mut Console) {
+    let input = console.input.clone();
+    let mut words: Vec<&str> = input.split_whitespace().collect();
+    let partial = if input.ends_with(' ') || input.is_empty() {
+        ""
+    } else {
+        words.pop().unwrap_or("")
+    };
+
+    let matches: Vec<&str> = completions(&words)
+        .iter()
+        .copied()
+        .filter(|candidate| candidate.starts_with(partial))
+        .collect();
+
+    let completed = match matches.as_slice() {
+        [] => return,
+        [only] => format!("{} ", only),
+        _ => {
+            console.print(matches.join("  "));
+            common_prefix(&matches)
+        }
+    };
+
+    let kept = &input[..input.len() - partial.len()];
+    console.input = format!("{}{}", kept, completed);
+}
+
+fn common_prefix(words: &[&str]) -> String {
+    let first = words[0];
+    let length = words.iter().fold(first.len(), |length, word| {
+        first
+            .chars()
+            .zip(word.chars())
+            .take(length)
+            .take_while(|(a, b)| a == b)
+            .count()
+    });
+
+    first[..length].to_string()
+}
+
+fn parse_color(name: &str) -> Option<SocketColor> {
+    match name.to_lowercase().as_str() {
+        "blue" => Some(SocketColor::BLUE),
+        "red" => Some(SocketColor::RED),
+        "green" => Some(SocketColor::GREEN),
+        "orange" => Some(SocketColor::ORANGE),
+        "pink" => Some(SocketColor::PINK),
+        _ => None,
+    }
+}
+
+fn parse_upgrade(words: &[&str]) -> Option<UpgradeKind> {
+    match words {
+        ["socket", level] => Some(UpgradeKind::AddSocket(AddSocketUpgrade {
+            level: level.parse().ok()?,
+        })),
+        ["ring", level] => Some(UpgradeKind::AddRing(AddRingUpgrade {
+            level: level.parse().ok()?,
+        })),
+        ["color", color] => Some(UpgradeKind::AddColor(AddColorUpgrade {
+            color: parse_color(color)?,
+        })),
+        ["enhance", color, tier] => Some(UpgradeKind::EnhanceColor(EnhanceColorUpgrade {
+            color: parse_color(color)?,
+            tier: tier.parse().ok()?,
+        })),
+        ["win"] => Some(UpgradeKind::Win),
+        _ => None,
+    }
+}
+
+fn run_console_command(
+    trigger: Trigger<RunConsoleCommand>,
+    mut commands: Commands,
+    mut console: ResMut<Console>,
+    in_run: Option<Res<State<InRun>>>,
+    run: (ResMut<Currency>, Res<UpgradeHistory>, ResMut<RunStats>),
+    mut ring_index: ResMut<RingIndex>,
+    materials: (ResMut<Assets<RingMaterial>>, ResMut<Assets<SocketMaterial>>),
+    gameplay_meshes: Res<GameplayMeshes>,
+    times: (Res<Time>, ResMut<Time<Virtual>>),
+    mut q_ring: Query<&mut Ring>,
+    mut q_socket: Query<(&mut Socket, &Handle<SocketMaterial>)>,
+    q_shop_item: Query<(Entity, &ShopItem)>,
+    achievements: Res<Achievements>,
+    settings: Res<Settings>,
+) {
+    let (mut currency, upgrade_history, mut run_stats) = run;
+    let (mut ring_materials, mut socket_materials) = materials;
+    let (time, mut virtual_time) = times;
+
+    let line = trigger.event().0.to_lowercase();
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let money = |amount: &BigUint| format!("${}", format_number(amount, settings.number_format));
+
+    let needs_run = !matches!(
+        words.as_slice(),
+        ["timescale", ..] | ["help"] | ["clear"]
+    );
+    if needs_run && in_run.is_none() {
+        console.print("Start a run first.");
+        return;
+    }
+
+    match words.as_slice() {
+        ["give", amount] => {
+            let Some(amount) = BigUint::parse_bytes(amount.as_bytes(), 10) else {
+                console.print(format!("Not a whole number: {}", amount));
+                return;
+            };
+
+            currency.amount += &amount;
+            run_stats.cheated = true;
+            console.print(format!("Gave {}", money(&amount)));
+        }
+        ["unlock", upgrade @ ..] => {
+            let Some(upgrade_kind) = parse_upgrade(upgrade) else {
+                console.print("Unknown upgrade, see `help`.");
+                return;
+            };
+
+            // granted like any other purchase, taking its shop button with it if it's for sale
+            let upgrade_button_entity = q_shop_item
+                .iter()
+                .find(|(_, item)| item.upgrade_kind == upgrade_kind)
+                .map_or(Entity::PLACEHOLDER, |(entity, _)| entity);
+
+            // marked before the purchase goes through, so it can't earn achievements or records
+            run_stats.cheated = true;
+            commands.trigger(Purchase {
+                upgrade: Upgrade::free(upgrade_kind),
+                upgrade_button_entity,
+            });
+
+            console.print(format!("Unlocked {}", upgrade.join(" ")));
+        }
+        ["ring", "add"] => {
+            let ring_count = q_ring.iter().count();
+
+            spawn_ring(
+                &mut commands,
+                &mut ring_index,
+                gameplay_meshes.quad512.clone(),
+                gameplay_meshes.quad64.clone(),
+                ring_materials.add(RingMaterial {
+                    data: Vec4::new(RING_RADIUS, RING_THICKNESS, 0., 0.),
+                }),
+                &mut socket_materials,
+                NEW_RING_SOCKETS.to_vec(),
+                &time,
+                ring_count,
+            );
+
+            run_stats.cheated = true;
+            console.print(format!("Added ring {}", ring_count + 1));
+        }
+        ["socket", "fill", ring_number, color] => {
+            let Some(color) = parse_color(color) else {
+                console.print(format!("Unknown color: {}", color));
+                return;
+            };
+            let Some(ring) = ring_number
+                .parse::<usize>()
+                .ok()
+                .and_then(|number| q_ring.iter().find(|ring| ring.index + 1 == number))
+            else {
+                console.print(format!("No ring {}", ring_number));
+                return;
+            };
+
+            for socket_entity in ring.sockets.clone() {
+                let Ok((mut socket, material_handle)) = q_socket.get_mut(socket_entity) else {
+                    continue;
+                };
+
+                socket.color = color;
+                socket.trigger_duration_seconds = map_socket_color_trigger_duration(color);
+
+                if let Some(material) = socket_materials.get_mut(material_handle) {
+                    material.inserted_color = map_socket_color(color);
+                    material.highlight_color = map_socket_highlight_color(color);
+                    material.data[1] = socket.trigger_duration_seconds;
+                    material.data[3] = (color as u8).saturating_sub(1) as f32;
+                }
+            }
+
+            run_stats.cheated = true;
+            console.print(format!("Filled ring {} with {}", ring_number, color.as_str()));
+        }
+        ["timescale", scale] => match scale.parse::<f32>() {
+            Ok(scale) if scale.is_finite() && scale >= 0. => {
+                virtual_time.set_relative_speed(scale);
+                if in_run.is_some() {
+                    run_stats.cheated = true;
+                }
+                console.print(format!("Time runs at {}x", scale));
+            }
+            _ => console.print(format!("Not a speed: {}", scale)),
+        },
+        ["skip", "cycles", count] => {
+            let Ok(count) = count.parse::<u32>() else {
+                console.print(format!("Not a whole number: {}", count));
+                return;
+            };
+
+            let mut rings: Vec<Mut<Ring>> = q_ring.iter_mut().collect();
+            rings.sort_by_key(|ring| ring.index);
+
+            let layouts: Vec<RingLayout> = rings
+                .iter()
+                .map(|ring| RingLayout {
+                    sockets: ring
+                        .sockets
+                        .iter()
+                        .map(|socket| {
+                            q_socket
+                                .get(*socket)
+                                .map_or(SocketColor::NONE, |(socket, _)| socket.color)
+                        })
+                        .collect(),
+                    cycle_duration: ring.cycle_duration,
+                })
+                .collect();
+
+            let per_cycle = estimate_income_per_cycle(
+                &layouts,
+                |kind| upgrade_history.history.contains(&kind),
+                &achievements.payout_multiplier(),
+            );
+            let earned = per_cycle * count;
+
+            for ring in &mut rings {
+                ring.cycle_count += count;
+            }
+            currency.amount += &earned;
+            run_stats.total_earned += &earned;
+            run_stats.cheated = true;
+
+            console.print(format!("Skipped {} cycles, earning {}", count, money(&earned)));
+        }
+        ["help"] => {
+            for line in HELP {
+                console.print(line);
+            }
+        }
+        ["clear"] => console.output.clear(),
+        _ => console.print("Unknown command, try `help`."),
+    }
+}
+
+fn sync_console_panel(
+    mut commands: Commands,
+    console: Res<Console>,
+    q_panel: Query<Entity, With<ConsolePanel>>,
+    mut q_text: Query<&mut Text, With<ConsoleText>>,
+) {
+    if !console.open {
+        for panel in &q_panel {
+            commands.entity(panel).despawn_recursive();
+        }
+        return;
+    }
+
+    let shown = console.output.len().saturating_sub(CONSOLE_OUTPUT_LINES);
+    let output: String = console
+        .output
+        .iter()
+        .skip(shown)
+        .map(|line| format!("{}\n", line))
+        .collect();
+    let input = format!("> {}_", console.input);
+
+    if let Ok(mut text) = q_text.get_single_mut() {
+        text.sections[0].value = output;
+        text.sections[1].value = input;
+        return;
+    }
+
+    if !q_panel.is_empty() {
+        return;
+    }
+
+    // Bevy's built-in font, so the console works before ours has loaded
+    let style = |color: Srgba| TextStyle {
+        font_size: 16.,
+        color: color.into(),
+        ..default()
+    };
+
+    commands
+        .spawn((
+            Name::new("Console"),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Px(0.),
+                    width: Percent(100.),
+                    padding: UiRect::all(Px(8.)),
+                    ..default()
+                },
+                background_color: GRAY_900.with_alpha(0.9).into(),
+                z_index: ZIndex::Global(200),
+                ..default()
+            },
+            ConsolePanel,
+            Pickable::IGNORE,
+        ))
+        .with_children(|panel| {
+            panel.spawn((
+                TextBundle::from_sections([
+                    TextSection::new(output, style(GRAY_400)),
+                    TextSection::new(input, style(WHITE)),
+                ]),
+                ConsoleText,
+            ));
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upgrades_parse_from_their_words() {
+        assert!(
+            parse_upgrade(&["socket", "3"])
+                == Some(UpgradeKind::AddSocket(AddSocketUpgrade { level: 3 }))
+        );
+        assert!(
+            parse_upgrade(&["ring", "2"])
+                == Some(UpgradeKind::AddRing(AddRingUpgrade { level: 2 }))
+        );
+        assert!(
+            parse_upgrade(&["color", "pink"])
+                == Some(UpgradeKind::AddColor(AddColorUpgrade {
+                    color: SocketColor::PINK
+                }))
+        );
+        assert!(
+            parse_upgrade(&["enhance", "red", "1"])
+                == Some(UpgradeKind::EnhanceColor(EnhanceColorUpgrade {
+                    color: SocketColor::RED,
+                    tier: 1,
+                }))
+        );
+        assert!(parse_upgrade(&["win"]) == Some(UpgradeKind::Win));
+    }
+
+    #[test]
+    fn bad_upgrades_dont_parse() {
+        assert!(parse_upgrade(&[]).is_none());
+        assert!(parse_upgrade(&["socket"]).is_none());
+        assert!(parse_upgrade(&["socket", "-1"]).is_none());
+        assert!(parse_upgrade(&["ring", "many"]).is_none());
+        assert!(parse_upgrade(&["color", "purple"]).is_none());
+        assert!(parse_upgrade(&["enhance", "red"]).is_none());
+        assert!(parse_upgrade(&["win", "now"]).is_none());
+    }
+
+    #[test]
+    fn completions_follow_the_command_so_far() {
+        assert_eq!(completions(&[]), COMMANDS);
+        assert_eq!(completions(&["ring"]), ["add"]);
+        assert_eq!(completions(&["unlock"]), UPGRADES);
+        assert_eq!(completions(&["unlock", "enhance"]), COLORS);
+        assert_eq!(completions(&["socket", "fill", "2"]), COLORS);
+        assert!(completions(&["unlock", "socket"]).is_empty());
+        assert!(completions(&["give", "100"]).is_empty());
+    }
+
+    #[test]
+    fn common_prefix_stops_at_the_first_difference() {
+        assert_eq!(common_prefix(&["socket", "skip"]), "s");
+        assert_eq!(common_prefix(&["ring", "red"]), "r");
+        assert_eq!(common_prefix(&["green", "give"]), "g");
+        assert_eq!(common_prefix(&["unlock"]), "unlock");
+        assert_eq!(common_prefix(&["clear", "clearly"]), "clear");
+        assert_eq!(common_prefix(&["blue", "orange"]), "");
+    }
+}
@@ -0,0 +1,332 @@
+//! Achievements, unlocked by playing and kept across runs.
+//!
+//! Each one is a row in [`ACHIEVEMENTS`]: its name, the condition that unlocks it and the
+//! permanent bonus it grants. Conditions are checked whenever a socket triggers, a cycle
+//! is paid out or an upgrade is bought, against whichever ring was involved.
+
+use std::collections::HashSet;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    screen::playing::{CyclePaidOut, RunStats, SocketTriggered},
+    ui::shop::{Purchase, UpgradeHistory, UpgradeKind},
+};
+
+use super::{
+    multiplier::Multiplier,
+    persistence,
+    spawn::level::{CycleTrigger, Ring, RingIndex, Socket, SocketColor},
+};
+
+const ACHIEVEMENTS_FILE: &str = "achievements.ron";
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(persistence::load::<Achievements>(ACHIEVEMENTS_FILE).unwrap_or_default());
+
+    app.observe(check_on_socket_triggered);
+    app.observe(check_on_cycle_paid_out);
+    app.observe(check_on_purchase);
+
+    app.add_systems(Update, save_achievements);
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum AchievementKey {
+    FirstPayout,
+    ChainReaction,
+    Domino,
+    Millionaire,
+    Trillionaire,
+    RingLeader,
+    LordOfTheRings,
+    ThinkPink,
+    Multiplied,
+    Win,
+}
+
+/// What has to happen for an achievement to unlock.
+#[derive(Clone, Copy)]
+pub enum AchievementCondition {
+    /// A ring triggers at least this many RED sockets in a row within one cycle.
+    RedChain { length: usize },
+    /// A ring pays out at least `10^power_of_ten` in one cycle.
+    CyclePayout { power_of_ten: u32 },
+    /// At least this many rings.
+    RingsOwned { count: usize },
+    /// A ring with at least `min_sockets` sockets, all of them `color`.
+    AllSockets { color: SocketColor, min_sockets: usize },
+    /// A ring's cycle multiplier reaches this much.
+    CycleMultiplier { at_least: u32 },
+    /// The upgrade has been bought.
+    Purchased(UpgradeKind),
+}
+
+/// What an achievement grants once it's unlocked, from then on in every run.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AchievementBonus {
+    /// Every cycle pays out this many percent more.
+    Payout { percent: u32 },
+}
+
+impl AchievementBonus {
+    pub fn description(&self) -> String {
+        match self {
+            AchievementBonus::Payout { percent } => format!("+{}% cycle payouts", percent),
+        }
+    }
+}
+
+pub struct Achievement {
+    pub key: AchievementKey,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub condition: AchievementCondition,
+    pub bonus: AchievementBonus,
+}
+
+pub static ACHIEVEMENTS: [Achievement; 10] = [
+    Achievement {
+        key: AchievementKey::FirstPayout,
+        name: "Every Few Seconds",
+        description: "Finish a cycle that pays out.",
+        condition: AchievementCondition::CyclePayout { power_of_ten: 0 },
+        bonus: AchievementBonus::Payout { percent: 1 },
+    },
+    Achievement {
+        key: AchievementKey::ChainReaction,
+        name: "Chain Reaction",
+        description: "Trigger 3 RED sockets in a row.",
+        condition: AchievementCondition::RedChain { length: 3 },
+        bonus: AchievementBonus::Payout { percent: 1 },
+    },
+    Achievement {
+        key: AchievementKey::Domino,
+        name: "Domino Effect",
+        description: "Trigger 6 RED sockets in a row.",
+        condition: AchievementCondition::RedChain { length: 6 },
+        bonus: AchievementBonus::Payout { percent: 2 },
+    },
+    Achievement {
+        key: AchievementKey::Millionaire,
+        name: "Millionaire Minute",
+        description: "Earn $1e6 from a single cycle.",
+        condition: AchievementCondition::CyclePayout { power_of_ten: 6 },
+        bonus: AchievementBonus::Payout { percent: 2 },
+    },
+    Achievement {
+        key: AchievementKey::Trillionaire,
+        name: "Trillionaire Tick",
+        description: "Earn $1e12 from a single cycle.",
+        condition: AchievementCondition::CyclePayout { power_of_ten: 12 },
+        bonus: AchievementBonus::Payout { percent: 3 },
+    },
+    Achievement {
+        key: AchievementKey::RingLeader,
+        name: "Ring Leader",
+        description: "Own 3 rings.",
+        condition: AchievementCondition::RingsOwned { count: 3 },
+        bonus: AchievementBonus::Payout { percent: 1 },
+    },
+    Achievement {
+        key: AchievementKey::LordOfTheRings,
+        name: "Lord of the Rings",
+        description: "Own 10 rings.",
+        condition: AchievementCondition::RingsOwned { count: 10 },
+        bonus: AchievementBonus::Payout { percent: 3 },
+    },
+    Achievement {
+        key: AchievementKey::ThinkPink,
+        name: "Think Pink",
+        description: "Fill a ring of 4 or more sockets with only PINK.",
+        condition: AchievementCondition::AllSockets {
+            color: SocketColor::PINK,
+            min_sockets: 4,
+        },
+        bonus: AchievementBonus::Payout { percent: 2 },
+    },
+    Achievement {
+        key: AchievementKey::Multiplied,
+        name: "Multiplied",
+        description: "Reach a x10 cycle multiplier.",
+        condition: AchievementCondition::CycleMultiplier { at_least: 10 },
+        bonus: AchievementBonus::Payout { percent: 2 },
+    },
+    Achievement {
+        key: AchievementKey::Win,
+        name: "Every Few Seconds, Forever",
+        description: "Buy the win.",
+        condition: AchievementCondition::Purchased(UpgradeKind::Win),
+        bonus: AchievementBonus::Payout { percent: 5 },
+    },
+];
+
+/// The achievements unlocked so far, across every run.
+#[derive(Resource, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Achievements {
+    pub unlocked: HashSet<AchievementKey>,
+}
+
+impl Achievements {
+    pub fn is_unlocked(&self, key: AchievementKey) -> bool {
+        self.unlocked.contains(&key)
+    }
+
+    /// What every cycle's payout is multiplied by, from the bonuses of everything unlocked.
+    pub fn payout_multiplier(&self) -> Multiplier {
+        let percent: u32 = ACHIEVEMENTS
+            .iter()
+            .filter(|achievement| self.is_unlocked(achievement.key))
+            .map(|achievement| match achievement.bonus {
+                AchievementBonus::Payout { percent } => percent,
+            })
+            .sum();
+
+        Multiplier::from_millionths(1_000_000 + percent * 10_000)
+    }
+}
+
+/// Triggered once when an achievement unlocks.
+#[derive(Event)]
+pub struct AchievementUnlocked {
+    pub key: AchievementKey,
+}
+
+pub fn achievement(key: AchievementKey) -> &'static Achievement {
+    ACHIEVEMENTS
+        .iter()
+        .find(|achievement| achievement.key == key)
+        .expect("Every AchievementKey should have an entry in ACHIEVEMENTS.")
+}
+
+/// The ring an event happened on, and what's currently slotted into it.
+struct RingSnapshot<'a> {
+    ring: &'a Ring,
+    socket_colors: Vec<SocketColor>,
+}
+
+impl AchievementCondition {
+    fn is_met(
+        &self,
+        ring: Option<&RingSnapshot>,
+        ring_count: usize,
+        upgrade_history: &UpgradeHistory,
+        just_purchased: Option<UpgradeKind>,
+        paid_out: Option<&CyclePaidOut>,
+    ) -> bool {
+        match *self {
+            AchievementCondition::RedChain { length } => ring.is_some_and(|ring| {
+                // once a cycle is paid out, what it triggered has moved to `previous_cycle`
+                longest_run(&ring.ring.cycle, SocketColor::RED).max(longest_run(
+                    &ring.ring.previous_cycle,
+                    SocketColor::RED,
+                )) >= length
+            }),
+            AchievementCondition::CyclePayout { power_of_ten } => paid_out
+                .is_some_and(|paid_out| paid_out.payout >= BigUint::from(10u32).pow(power_of_ten)),
+            AchievementCondition::RingsOwned { count } => ring_count >= count,
+            AchievementCondition::AllSockets { color, min_sockets } => ring.is_some_and(|ring| {
+                ring.socket_colors.len() >= min_sockets
+                    && ring.socket_colors.iter().all(|socket_color| *socket_color == color)
+            }),
+            AchievementCondition::CycleMultiplier { at_least } => {
+                let at_least = Multiplier::from_integer(at_least);
+
+                // a socket's check may run before or after the trigger it's for has been scored,
+                // but the payout always sees the cycle's final multiplier
+                ring.is_some_and(|ring| ring.ring.cycle_multiplier >= at_least)
+                    || paid_out.is_some_and(|paid_out| paid_out.multiplier >= at_least)
+            }
+            AchievementCondition::Purchased(upgrade_kind) => {
+                just_purchased == Some(upgrade_kind)
+                    || upgrade_history.history.contains(&upgrade_kind)
+            }
+        }
+    }
+}
+
+fn longest_run(cycle: &[CycleTrigger], color: SocketColor) -> usize {
+    cycle
+        .iter()
+        .fold((0, 0), |(longest, current), trigger| {
+            let current = if trigger.color == color { current + 1 } else { 0 };
+            (longest.max(current), current)
+        })
+        .0
+}
+
+#[derive(SystemParam)]
+struct AchievementCheck<'w, 's> {
+    commands: Commands<'w, 's>,
+    achievements: ResMut<'w, Achievements>,
+    ring_index: Res<'w, RingIndex>,
+    upgrade_history: Res<'w, UpgradeHistory>,
+    run_stats: Res<'w, RunStats>,
+    q_ring: Query<'w, 's, &'static Ring>,
+    q_socket: Query<'w, 's, &'static Socket>,
+}
+
+impl AchievementCheck<'_, '_> {
+    fn check(
+        &mut self,
+        ring_entity: Option<Entity>,
+        just_purchased: Option<UpgradeKind>,
+        paid_out: Option<&CyclePaidOut>,
+    ) {
+        if self.run_stats.cheated {
+            return;
+        }
+
+        let ring = ring_entity
+            .and_then(|entity| self.q_ring.get(entity).ok())
+            .map(|ring| RingSnapshot {
+                ring,
+                socket_colors: ring
+                    .sockets
+                    .iter()
+                    .filter_map(|socket| self.q_socket.get(*socket).ok())
+                    .map(|socket| socket.color)
+                    .collect(),
+            });
+
+        for achievement in ACHIEVEMENTS.iter() {
+            if self.achievements.is_unlocked(achievement.key) {
+                continue;
+            }
+
+            if achievement.condition.is_met(
+                ring.as_ref(),
+                self.ring_index.rings.len(),
+                &self.upgrade_history,
+                just_purchased,
+                paid_out,
+            ) {
+                self.achievements.unlocked.insert(achievement.key);
+                self.commands.trigger(AchievementUnlocked {
+                    key: achievement.key,
+                });
+            }
+        }
+    }
+}
+
+fn check_on_socket_triggered(trigger: Trigger<SocketTriggered>, mut check: AchievementCheck) {
+    check.check(Some(trigger.event().ring), None, None);
+}
+
+fn check_on_cycle_paid_out(trigger: Trigger<CyclePaidOut>, mut check: AchievementCheck) {
+    check.check(Some(trigger.event().ring), None, Some(trigger.event()));
+}
+
+fn check_on_purchase(trigger: Trigger<Purchase>, mut check: AchievementCheck) {
+    check.check(None, Some(trigger.event().upgrade.upgrade_kind), None);
+}
+
+fn save_achievements(achievements: Res<Achievements>) {
+    if achievements.is_changed() && !achievements.is_added() {
+        persistence::save(ACHIEVEMENTS_FILE, &*achievements);
+    }
+}
@@ -1,8 +1,11 @@
 use bevy::{
+    asset::{LoadState, RecursiveDependencyLoadState},
     prelude::*,
     utils::HashMap,
 };
 
+use super::credits::Credits;
+
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<HandleMap<ImageKey>>();
     app.init_resource::<HandleMap<ImageKey>>();
@@ -18,6 +21,9 @@ pub(super) fn plugin(app: &mut App) {
 
     app.register_type::<HandleMap<FontKey>>();
     app.init_resource::<HandleMap<FontKey>>();
+
+    app.register_type::<HandleMap<CreditsKey>>();
+    app.init_resource::<HandleMap<CreditsKey>>();
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Reflect)]
@@ -77,7 +83,7 @@ impl FromWorld for HandleMap<SfxKey> {
     }
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Reflect)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Reflect)]
 pub enum SoundtrackKey {
     Credits,
     Gameplay,
@@ -95,6 +101,11 @@ impl FromWorld for HandleMap<SoundtrackKey> {
                 SoundtrackKey::Gameplay,
                 asset_server.load("audio/soundtracks/soundtrack.ogg"),
             ),
+            // shares the gameplay track until the credits get one of their own
+            (
+                SoundtrackKey::Credits,
+                asset_server.load("audio/soundtracks/soundtrack.ogg"),
+            ),
         ]
         .into()
     }
@@ -105,31 +116,59 @@ pub enum ShaderKey {
     Ring,
     Hand,
     Socket,
+    SocketShapes,
+    SocketInstanced,
     UiSocket,
     Background,
+    GlobalTime,
 }
 
 impl AssetKey for ShaderKey {
     type Asset = Shader;
 }
 
+impl ShaderKey {
+    pub const ALL: [ShaderKey; 8] = [
+        ShaderKey::Ring,
+        ShaderKey::Hand,
+        ShaderKey::Socket,
+        ShaderKey::SocketShapes,
+        ShaderKey::SocketInstanced,
+        ShaderKey::UiSocket,
+        ShaderKey::Background,
+        ShaderKey::GlobalTime,
+    ];
+
+    pub fn path(&self) -> &'static str {
+        match self {
+            ShaderKey::Ring => "shaders/ring.wgsl",
+            ShaderKey::Hand => "shaders/hand.wgsl",
+            ShaderKey::Socket => "shaders/socket.wgsl",
+            ShaderKey::SocketShapes => "shaders/socket_shapes.wgsl",
+            ShaderKey::SocketInstanced => "shaders/socket_instanced.wgsl",
+            ShaderKey::UiSocket => "shaders/ui_socket.wgsl",
+            ShaderKey::Background => "shaders/background.wgsl",
+            ShaderKey::GlobalTime => "shaders/global_time.wgsl",
+        }
+    }
+}
+
 impl FromWorld for HandleMap<ShaderKey> {
     fn from_world(world: &mut World) -> Self {
+        // two keys sharing a path would leave one of the shaders untracked
+        debug_assert!(
+            ShaderKey::ALL.iter().enumerate().all(|(index, key)| {
+                ShaderKey::ALL[..index]
+                    .iter()
+                    .all(|other| other.path() != key.path())
+            }),
+            "Every ShaderKey needs its own path"
+        );
+
         let asset_server = world.resource::<AssetServer>();
-        [
-            (ShaderKey::Ring, asset_server.load("shaders/ring.wgsl")),
-            (ShaderKey::Hand, asset_server.load("shaders/hand.wgsl")),
-            (ShaderKey::Socket, asset_server.load("shaders/socket.wgsl")),
-            (
-                ShaderKey::UiSocket,
-                asset_server.load("shaders/ui_socket.wgsl"),
-            ),
-            (
-                ShaderKey::UiSocket,
-                asset_server.load("shaders/background.wgsl"),
-            ),
-        ]
-        .into()
+        ShaderKey::ALL
+            .map(|key| (key, asset_server.load(key.path())))
+            .into()
     }
 }
 
@@ -154,6 +193,22 @@ impl FromWorld for HandleMap<FontKey> {
     }
 }
 
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Reflect)]
+pub enum CreditsKey {
+    Credits,
+}
+
+impl AssetKey for CreditsKey {
+    type Asset = Credits;
+}
+
+impl FromWorld for HandleMap<CreditsKey> {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        [(CreditsKey::Credits, asset_server.load("credits.ron"))].into()
+    }
+}
+
 pub trait AssetKey: Sized {
     type Asset: Asset;
 }
@@ -176,4 +231,25 @@ impl<K: AssetKey> HandleMap<K> {
         self.values()
             .all(|x| asset_server.is_loaded_with_dependencies(x))
     }
+
+    /// How many of the handles are loaded, along with everything they depend on.
+    pub fn loaded_count(&self, asset_server: &AssetServer) -> usize {
+        self.values()
+            .filter(|x| asset_server.is_loaded_with_dependencies(*x))
+            .count()
+    }
+
+    /// Where the assets that failed to load, or that have a dependency that did, live.
+    pub fn failed_paths(&self, asset_server: &AssetServer) -> Vec<String> {
+        self.values()
+            .filter(|x| {
+                matches!(asset_server.get_load_state(*x), Some(LoadState::Failed(_)))
+                    || matches!(
+                        asset_server.get_recursive_dependency_load_state(*x),
+                        Some(RecursiveDependencyLoadState::Failed)
+                    )
+            })
+            .filter_map(|x| x.path().map(|path| path.to_string()))
+            .collect()
+    }
 }
@@ -0,0 +1,101 @@
+//! Volume buses. Every sound belongs to a bus, and plays at
+//! master volume × bus volume × the volume it was asked to play at.
+
+use bevy::prelude::*;
+
+use crate::game::settings::Settings;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<AudioMixer>();
+
+    app.add_systems(
+        Update,
+        (
+            sync_mixer_with_settings.run_if(resource_changed::<Settings>),
+            update_mixed_sinks,
+        )
+            .chain(),
+    );
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+pub enum AudioBus {
+    Music,
+    Sfx,
+    /// Feedback for things the player did directly, like buying upgrades or placing orbs.
+    Ui,
+}
+
+#[derive(Resource, Clone, PartialEq, Debug)]
+pub struct AudioMixer {
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+    pub ui: f32,
+    /// Silences every bus without stopping anything, so the soundtrack picks up where it was.
+    pub muted: bool,
+}
+
+impl Default for AudioMixer {
+    fn default() -> Self {
+        Self {
+            master: 1.,
+            music: 1.,
+            sfx: 1.,
+            ui: 1.,
+            muted: false,
+        }
+    }
+}
+
+impl AudioMixer {
+    pub fn bus_volume(&self, bus: AudioBus) -> f32 {
+        if self.muted {
+            return 0.;
+        }
+
+        self.master
+            * match bus {
+                AudioBus::Music => self.music,
+                AudioBus::Sfx => self.sfx,
+                AudioBus::Ui => self.ui,
+            }
+    }
+
+    pub fn volume(&self, mixed_audio: &MixedAudio) -> f32 {
+        self.bus_volume(mixed_audio.bus) * mixed_audio.volume
+    }
+}
+
+/// The bus a playing sound belongs to and the volume it was played at,
+/// kept around so its sink can be remixed when the bus volume changes.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct MixedAudio {
+    pub bus: AudioBus,
+    pub volume: f32,
+}
+
+fn sync_mixer_with_settings(settings: Res<Settings>, mut mixer: ResMut<AudioMixer>) {
+    let new_mixer = AudioMixer {
+        master: settings.master_volume,
+        music: settings.music_volume,
+        sfx: settings.sfx_volume,
+        ui: settings.ui_volume,
+        muted: mixer.muted,
+    };
+
+    mixer.set_if_neq(new_mixer);
+}
+
+/// `PlaybackSettings` are only read when a sound starts, so anything still playing
+/// (most importantly the looping soundtrack) needs its sink adjusted directly.
+fn update_mixed_sinks(
+    mixer: Res<AudioMixer>,
+    q_sinks: Query<(Ref<AudioSink>, Ref<MixedAudio>)>,
+) {
+    for (sink, mixed_audio) in &q_sinks {
+        if mixer.is_changed() || sink.is_added() || mixed_audio.is_changed() {
+            sink.set_volume(mixer.volume(&mixed_audio));
+        }
+    }
+}
@@ -1,7 +1,10 @@
+pub mod mixer;
+pub mod music;
 pub mod soundtrack;
+pub mod voices;
 
 use bevy::prelude::*;
 
 pub fn plugin(app: &mut App) {
-    app.add_plugins((soundtrack::plugin));
+    app.add_plugins((mixer::plugin, music::plugin, soundtrack::plugin, voices::plugin));
 }
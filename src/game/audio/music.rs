@@ -0,0 +1,211 @@
+//! Lets the rings play along with the soundtrack.
+//!
+//! Notes are asked for by chord tone rather than by sound effect. They wait for the
+//! next step on the soundtrack's beat grid, then get resolved against whichever chord
+//! the progression is on at that moment and pitched to match.
+
+use bevy::prelude::*;
+
+use crate::game::assets::SfxKey;
+
+use super::{
+    mixer::AudioBus,
+    soundtrack::{IsSoundtrack, PlaySfx},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<SoundtrackClock>();
+    app.init_resource::<ScheduledNotes>();
+    app.observe(on_note);
+    app.add_systems(Update, (update_soundtrack_clock, play_scheduled_notes).chain());
+}
+
+/// The gameplay soundtrack is 86.25 seconds long, which is exactly 46 bars at this tempo.
+const BEATS_PER_MINUTE: f32 = 128.;
+const BEATS_PER_BAR: f32 = 4.;
+const BARS_PER_CHORD: f32 = 2.;
+
+/// A note that lands this close after a grid step plays right away instead of waiting for the next one.
+const GRID_TOLERANCE_SECONDS: f32 = 0.01;
+
+/// Pitches are in semitones relative to the `cycle-c` sample.
+/// Each chord lists its tones from lowest to highest.
+const CHORD_PROGRESSION: [&[i32]; 4] = [
+    // C
+    &[-5, 0, 4, 7, 12],
+    // Am
+    &[-3, 0, 4, 9, 12],
+    // F
+    &[-7, -3, 0, 5, 9],
+    // G
+    &[-5, -1, 2, 7, 11],
+];
+
+/// The pitched chime samples and the pitch each one was recorded at.
+const CHIME_SAMPLES: [(SfxKey, i32); 6] = [
+    (SfxKey::CycleLowF, -7),
+    (SfxKey::CycleLowG, -5),
+    (SfxKey::CycleC, 0),
+    (SfxKey::CycleD, 2),
+    (SfxKey::CycleHighF, 5),
+    (SfxKey::CycleHighG, 7),
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Instrument {
+    /// The ringing tones played when a ring finishes a cycle. Snaps to eighth notes.
+    Chime,
+    /// The short clicks played when a socket triggers. Snaps to sixteenth notes.
+    Click(SfxKey),
+}
+
+impl Instrument {
+    fn grid_beats(&self) -> f32 {
+        match self {
+            Instrument::Chime => 0.5,
+            Instrument::Click(_) => 0.25,
+        }
+    }
+}
+
+/// Trigger this event to play a note in time with the soundtrack.
+/// If no soundtrack is playing, the note plays immediately.
+#[derive(Event)]
+pub struct PlayNote {
+    pub instrument: Instrument,
+    /// Which tone of the current chord to play, counting up from the lowest. Wraps around.
+    pub chord_tone: usize,
+    pub bus: AudioBus,
+    pub volume: f32,
+    pub position: Option<Vec3>,
+}
+
+/// When the current soundtrack started playing, in real time.
+#[derive(Resource, Default)]
+pub struct SoundtrackClock {
+    started_seconds: Option<f32>,
+}
+
+impl SoundtrackClock {
+    fn beats_at(&self, seconds: f32) -> Option<f32> {
+        self.started_seconds
+            .map(|started| (seconds - started) * BEATS_PER_MINUTE / 60.)
+    }
+
+    /// The first grid step, `grid_beats` apart, at or after `seconds`.
+    fn next_grid_seconds(&self, seconds: f32, grid_beats: f32) -> f32 {
+        let (Some(started), Some(beats)) = (self.started_seconds, self.beats_at(seconds)) else {
+            return seconds;
+        };
+
+        let tolerance_beats = GRID_TOLERANCE_SECONDS * BEATS_PER_MINUTE / 60.;
+        let steps = ((beats - tolerance_beats) / grid_beats).ceil().max(0.);
+
+        (started + steps * grid_beats * 60. / BEATS_PER_MINUTE).max(seconds)
+    }
+
+    fn chord_at(&self, seconds: f32) -> &'static [i32] {
+        let beats = self.beats_at(seconds).unwrap_or(0.).max(0.);
+        let chord_index = (beats / (BEATS_PER_BAR * BARS_PER_CHORD)) as usize;
+
+        CHORD_PROGRESSION[chord_index % CHORD_PROGRESSION.len()]
+    }
+}
+
+struct ScheduledNote {
+    play_at_seconds: f32,
+    instrument: Instrument,
+    chord_tone: usize,
+    bus: AudioBus,
+    volume: f32,
+    position: Option<Vec3>,
+}
+
+#[derive(Resource, Default)]
+struct ScheduledNotes(Vec<ScheduledNote>);
+
+fn update_soundtrack_clock(
+    mut clock: ResMut<SoundtrackClock>,
+    q_started: Query<(), (Added<AudioSink>, With<IsSoundtrack>)>,
+    q_soundtrack: Query<(), With<IsSoundtrack>>,
+    time: Res<Time<Real>>,
+) {
+    // the sink shows up once the track is actually playing, which is the best guess at beat zero
+    if !q_started.is_empty() {
+        clock.started_seconds = Some(time.elapsed_seconds());
+    } else if q_soundtrack.is_empty() && clock.started_seconds.is_some() {
+        clock.started_seconds = None;
+    }
+}
+
+fn on_note(
+    trigger: Trigger<PlayNote>,
+    clock: Res<SoundtrackClock>,
+    mut scheduled_notes: ResMut<ScheduledNotes>,
+    time: Res<Time<Real>>,
+) {
+    let note = trigger.event();
+
+    scheduled_notes.0.push(ScheduledNote {
+        play_at_seconds: clock
+            .next_grid_seconds(time.elapsed_seconds(), note.instrument.grid_beats()),
+        instrument: note.instrument,
+        chord_tone: note.chord_tone,
+        bus: note.bus,
+        volume: note.volume,
+        position: note.position,
+    });
+}
+
+fn play_scheduled_notes(
+    mut commands: Commands,
+    clock: Res<SoundtrackClock>,
+    mut scheduled_notes: ResMut<ScheduledNotes>,
+    time: Res<Time<Real>>,
+) {
+    let now = time.elapsed_seconds();
+
+    scheduled_notes.0.retain(|note| {
+        if note.play_at_seconds > now {
+            return true;
+        }
+
+        let chord = clock.chord_at(note.play_at_seconds);
+        let pitch = chord[note.chord_tone % chord.len()];
+        let (key, speed) = match note.instrument {
+            Instrument::Chime => chime_for_pitch(pitch),
+            Instrument::Click(key) => (key, click_speed_for_pitch(pitch)),
+        };
+
+        commands.trigger(PlaySfx {
+            key,
+            bus: note.bus,
+            volume: note.volume,
+            speed,
+            position: note.position,
+        });
+
+        false
+    });
+}
+
+fn semitones_to_speed(semitones: i32) -> f32 {
+    2f32.powf(semitones as f32 / 12.)
+}
+
+/// Picks the chime sample closest to `pitch` and how fast to play it to land on `pitch` exactly.
+fn chime_for_pitch(pitch: i32) -> (SfxKey, f32) {
+    let (key, sample_pitch) = CHIME_SAMPLES
+        .iter()
+        .min_by_key(|(_, sample_pitch)| (pitch - sample_pitch).abs())
+        .copied()
+        .unwrap();
+
+    (key, semitones_to_speed(pitch - sample_pitch))
+}
+
+/// Clicks aren't recorded at any pitch, so only the note name matters.
+/// Fold it into the half octave either side so they never get too squeaky or muddy.
+fn click_speed_for_pitch(pitch: i32) -> f32 {
+    semitones_to_speed((pitch + 6).rem_euclid(12) - 6)
+}
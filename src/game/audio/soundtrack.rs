@@ -1,42 +1,157 @@
 use bevy::{audio::{PlaybackMode, Volume}, prelude::*};
 
-use crate::{game::assets::{HandleMap, SfxKey, SoundtrackKey}, ui::scoreboard::AudioSettings};
+use crate::game::assets::{HandleMap, SfxKey, SoundtrackKey};
+
+use super::{
+    mixer::{AudioBus, AudioMixer, MixedAudio},
+    voices::{voice_priority, VoiceDecision, VoiceManager},
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<IsSoundtrack>();
+    app.init_resource::<SoundtrackCrossfade>();
+    app.init_resource::<SoundtrackIntensity>();
     app.observe(play_soundtrack);
     app.observe(on_sfx);
+    app.add_systems(Update, (fade_soundtracks, mix_soundtracks).chain());
+}
+
+/// How long the old soundtrack takes to fade out while the new one fades in.
+#[derive(Resource)]
+pub struct SoundtrackCrossfade {
+    pub seconds: f32,
+}
+
+impl Default for SoundtrackCrossfade {
+    fn default() -> Self {
+        Self { seconds: 2. }
+    }
+}
+
+/// How much is going on in the run. The gameplay soundtrack gets louder as this goes up.
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum SoundtrackIntensity {
+    #[default]
+    Calm,
+    Busy,
+    Frantic,
+}
+
+impl SoundtrackIntensity {
+    /// The volume of the intensity layer for a soundtrack, before fading.
+    fn layer_volume(&self, key: SoundtrackKey) -> f32 {
+        match key {
+            SoundtrackKey::Gameplay => match self {
+                SoundtrackIntensity::Calm => 0.6,
+                SoundtrackIntensity::Busy => 0.8,
+                SoundtrackIntensity::Frantic => 1.,
+            },
+            SoundtrackKey::Credits => 1.,
+        }
+    }
 }
 
 fn play_soundtrack(
     trigger: Trigger<PlaySoundtrack>,
     mut commands: Commands,
     soundtrack_handles: Res<HandleMap<SoundtrackKey>>,
-    soundtrack_query: Query<Entity, With<IsSoundtrack>>,
+    soundtrack_query: Query<(Entity, &IsSoundtrack, &SoundtrackMix, Has<FadingOut>)>,
+    mixer: Res<AudioMixer>,
+    crossfade: Res<SoundtrackCrossfade>,
+    intensity: Res<SoundtrackIntensity>,
 ) {
-    for entity in &soundtrack_query {
-        commands.entity(entity).despawn_recursive();
+    let soundtrack = match trigger.event() {
+        PlaySoundtrack::Key(key) => resolve_soundtrack(*key, &soundtrack_handles),
+        PlaySoundtrack::Disable => None,
+    };
+    let soundtrack_key = soundtrack.as_ref().map(|(key, _)| *key);
+
+    // asking for the track that's already playing shouldn't restart it,
+    // and asking for one that's on its way out brings it back
+    let mut kept = None;
+
+    for (entity, soundtrack, mix, fading_out) in &soundtrack_query {
+        if kept.is_none() && Some(soundtrack.key) == soundtrack_key {
+            kept = Some(entity);
+
+            if fading_out {
+                commands
+                    .entity(entity)
+                    .remove::<FadingOut>()
+                    .insert(SoundtrackFade::new(mix.fade, 1., crossfade.seconds));
+            }
+
+            continue;
+        }
+
+        if !fading_out {
+            commands.entity(entity).insert((
+                SoundtrackFade::new(mix.fade, 0., crossfade.seconds),
+                FadingOut,
+            ));
+        }
+    }
+
+    if kept.is_some() {
+        return;
     }
 
-    let soundtrack_key = match trigger.event() {
-        PlaySoundtrack::Key(key) => *key,
-        PlaySoundtrack::Disable => return,
+    let Some((soundtrack_key, source)) = soundtrack else {
+        return;
+    };
+
+    let mix = SoundtrackMix {
+        fade: 0.,
+        layer_volume: intensity.layer_volume(soundtrack_key),
     };
-    
+
+    let mixed_audio = MixedAudio {
+        bus: AudioBus::Music,
+        volume: mix.volume(),
+    };
+
     commands.spawn((
         AudioSourceBundle {
-            source: soundtrack_handles[&soundtrack_key].clone_weak(),
+            source,
             settings: PlaybackSettings {
                 mode: PlaybackMode::Loop,
+                volume: Volume::new(mixer.volume(&mixed_audio)),
                 ..default()
             },
         },
-        IsSoundtrack,
+        mixed_audio,
+        mix,
+        SoundtrackFade::new(0., 1., crossfade.seconds),
+        IsSoundtrack { key: soundtrack_key },
     ));
 }
 
+/// The soundtrack to actually play for `key`. Anything without an asset falls back to the gameplay soundtrack.
+fn resolve_soundtrack(
+    key: SoundtrackKey,
+    soundtrack_handles: &HandleMap<SoundtrackKey>,
+) -> Option<(SoundtrackKey, Handle<AudioSource>)> {
+    if let Some(handle) = soundtrack_handles.get(&key) {
+        return Some((key, handle.clone_weak()));
+    }
+
+    if let Some(handle) = soundtrack_handles.get(&SoundtrackKey::Gameplay) {
+        warn!(
+            "No soundtrack asset for {:?}, playing the gameplay soundtrack instead.",
+            key
+        );
+        return Some((SoundtrackKey::Gameplay, handle.clone_weak()));
+    }
+
+    warn!(
+        "No soundtrack asset for {:?} or the gameplay soundtrack.",
+        key
+    );
+    None
+}
+
 /// Trigger this event to play or disable the soundtrack.
-/// Playing a new soundtrack will overwrite the previous one.
+/// Playing a new soundtrack crossfades from the previous one.
 /// Soundtracks will loop.
 #[derive(Event)]
 pub enum PlaySoundtrack {
@@ -47,30 +162,157 @@ pub enum PlaySoundtrack {
 /// Marker component for the soundtrack entity so we can find it later.
 #[derive(Component, Reflect)]
 #[reflect(Component)]
-struct IsSoundtrack;
+pub struct IsSoundtrack {
+    pub key: SoundtrackKey,
+}
+
+/// A soundtrack on its way out. It despawns once it's silent.
+#[derive(Component)]
+pub struct FadingOut;
+
+/// The two volumes a soundtrack plays at, multiplied together:
+/// how far it's faded in, and how loud its intensity layer is.
+#[derive(Component)]
+struct SoundtrackMix {
+    fade: f32,
+    layer_volume: f32,
+}
+
+impl SoundtrackMix {
+    fn volume(&self) -> f32 {
+        self.fade * self.layer_volume
+    }
+}
+
+#[derive(Component)]
+struct SoundtrackFade {
+    from: f32,
+    to: f32,
+    elapsed_seconds: f32,
+    duration_seconds: f32,
+}
+
+impl SoundtrackFade {
+    fn new(from: f32, to: f32, duration_seconds: f32) -> Self {
+        Self {
+            from,
+            to,
+            elapsed_seconds: 0.,
+            duration_seconds,
+        }
+    }
+}
+
+fn fade_soundtracks(
+    mut commands: Commands,
+    mut q_fading: Query<(Entity, &mut SoundtrackFade, &mut SoundtrackMix, Has<FadingOut>)>,
+    time: Res<Time<Real>>,
+) {
+    for (entity, mut fade, mut mix, fading_out) in &mut q_fading {
+        fade.elapsed_seconds += time.delta_seconds();
+
+        let progress = if fade.duration_seconds > 0. {
+            (fade.elapsed_seconds / fade.duration_seconds).min(1.)
+        } else {
+            1.
+        };
+
+        mix.fade = fade.from.lerp(fade.to, progress);
+
+        if progress >= 1. {
+            if fading_out {
+                commands.entity(entity).despawn_recursive();
+            } else {
+                commands.entity(entity).remove::<SoundtrackFade>();
+            }
+        }
+    }
+}
 
+/// Moves each soundtrack's intensity layer towards the current intensity, over the crossfade time,
+/// and remixes it.
+fn mix_soundtracks(
+    mut q_soundtracks: Query<(&IsSoundtrack, &mut SoundtrackMix, &mut MixedAudio)>,
+    intensity: Res<SoundtrackIntensity>,
+    crossfade: Res<SoundtrackCrossfade>,
+    time: Res<Time<Real>>,
+) {
+    let max_step = if crossfade.seconds > 0. {
+        time.delta_seconds() / crossfade.seconds
+    } else {
+        f32::INFINITY
+    };
+
+    for (soundtrack, mut mix, mut mixed_audio) in &mut q_soundtracks {
+        let target = intensity.layer_volume(soundtrack.key);
+        let step = (target - mix.layer_volume).clamp(-max_step, max_step);
+        mix.layer_volume += step;
+
+        let volume = mix.volume();
+        if mixed_audio.volume != volume {
+            mixed_audio.volume = volume;
+        }
+    }
+}
 
 #[derive(Event)]
 pub struct PlaySfx {
     pub key: SfxKey,
+    pub bus: AudioBus,
     pub volume: f32,
+    /// Playback speed, which also shifts the pitch. `1.` plays the sound as recorded.
+    pub speed: f32,
+    /// Where in the world the sound comes from. Sounds on screen win out over ones that aren't.
+    pub position: Option<Vec3>,
 }
 
 fn on_sfx(
     trigger: Trigger<PlaySfx>,
     mut commands: Commands,
     sfx_handles: Res<HandleMap<SfxKey>>,
-    audio_settings: Res<AudioSettings>,
+    mixer: Res<AudioMixer>,
+    mut voice_manager: ResMut<VoiceManager>,
+    q_camera: Query<(&Camera, &GlobalTransform), With<IsDefaultUiCamera>>,
+    time: Res<Time<Real>>,
 ) {
-    if audio_settings.enabled {
-        commands.spawn(AudioSourceBundle {
-            source: sfx_handles[&trigger.event().key].clone_weak(),
-            settings: PlaybackSettings {
-                mode: PlaybackMode::Despawn,
-                volume: Volume::new(trigger.event().volume),
-                ..default()
-            },
-        });
+    if mixer.muted {
+        return;
     }
 
-}
\ No newline at end of file
+    let key = trigger.event().key;
+    let now = time.elapsed_seconds();
+    let priority = voice_priority(key, trigger.event().position, q_camera.get_single().ok());
+
+    match voice_manager.request_voice(key, priority, now) {
+        VoiceDecision::Drop => return,
+        VoiceDecision::Steal(entity) => {
+            // the voice may have finished and despawned itself since it was last seen
+            if let Some(voice) = commands.get_entity(entity) {
+                voice.despawn_recursive();
+            }
+        }
+        VoiceDecision::Play => {}
+    }
+
+    let mixed_audio = MixedAudio {
+        bus: trigger.event().bus,
+        volume: trigger.event().volume,
+    };
+
+    let voice = commands
+        .spawn((
+            AudioSourceBundle {
+                source: sfx_handles[&key].clone_weak(),
+                settings: PlaybackSettings {
+                    mode: PlaybackMode::Despawn,
+                    volume: Volume::new(mixer.volume(&mixed_audio)),
+                    speed: trigger.event().speed,
+                    ..default()
+                },
+            },
+            mixed_audio,
+        ))
+        .id();
+
+    voice_manager.add_voice(voice, key, priority, now);
+}
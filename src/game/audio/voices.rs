@@ -0,0 +1,173 @@
+//! Voice limiting for sound effects.
+//!
+//! With enough rings, a single frame can ask for dozens of clicks and cycle chimes.
+//! Every [`SfxKey`] gets a cap on how many copies may play at once and a cooldown
+//! between starts. When a key is at its cap, a new sound may steal the voice of the
+//! lowest priority one that is already playing. Sounds from rings on screen outrank
+//! sounds from rings the player can't see.
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::game::assets::SfxKey;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<VoiceManager>();
+    app.add_systems(PostUpdate, forget_finished_voices);
+}
+
+/// No matter how the per-key limits add up, never play more than this many effects at once.
+const MAX_TOTAL_VOICES: usize = 24;
+
+/// How much a sound gains by coming from somewhere on screen.
+const VISIBLE_PRIORITY_BONUS: f32 = 10.;
+
+pub struct VoiceLimit {
+    pub max_voices: usize,
+    pub cooldown_seconds: f32,
+    pub priority: f32,
+}
+
+pub fn voice_limit(key: SfxKey) -> VoiceLimit {
+    match key {
+        SfxKey::Unlock | SfxKey::Affirm | SfxKey::Neg => VoiceLimit {
+            max_voices: 2,
+            cooldown_seconds: 0.,
+            priority: 100.,
+        },
+        SfxKey::Click | SfxKey::Click2 | SfxKey::Click3 => VoiceLimit {
+            max_voices: 4,
+            cooldown_seconds: 0.05,
+            priority: 1.,
+        },
+        SfxKey::CycleC
+        | SfxKey::CycleD
+        | SfxKey::CycleLowF
+        | SfxKey::CycleLowG
+        | SfxKey::CycleHighF
+        | SfxKey::CycleHighG => VoiceLimit {
+            max_voices: 2,
+            cooldown_seconds: 0.1,
+            priority: 5.,
+        },
+    }
+}
+
+struct ActiveVoice {
+    entity: Entity,
+    key: SfxKey,
+    priority: f32,
+    started_seconds: f32,
+}
+
+pub enum VoiceDecision {
+    Play,
+    /// Play, after stopping the voice that is currently playing on this entity.
+    Steal(Entity),
+    Drop,
+}
+
+#[derive(Resource, Default)]
+pub struct VoiceManager {
+    voices: Vec<ActiveVoice>,
+    last_started_seconds: HashMap<SfxKey, f32>,
+}
+
+impl VoiceManager {
+    /// Decide whether a sound should play. The caller must follow up a
+    /// [`VoiceDecision::Play`] or [`VoiceDecision::Steal`] with [`VoiceManager::add_voice`].
+    pub fn request_voice(&mut self, key: SfxKey, priority: f32, now_seconds: f32) -> VoiceDecision {
+        let limit = voice_limit(key);
+
+        if let Some(last_started) = self.last_started_seconds.get(&key) {
+            if now_seconds - last_started < limit.cooldown_seconds {
+                return VoiceDecision::Drop;
+            }
+        }
+
+        let key_voice_count = self.voices.iter().filter(|voice| voice.key == key).count();
+
+        let steal_candidate = if key_voice_count >= limit.max_voices {
+            self.weakest_voice(|voice| voice.key == key)
+        } else if self.voices.len() >= MAX_TOTAL_VOICES {
+            self.weakest_voice(|_| true)
+        } else {
+            return VoiceDecision::Play;
+        };
+
+        match steal_candidate {
+            Some(index) if self.voices[index].priority < priority => {
+                VoiceDecision::Steal(self.voices.swap_remove(index).entity)
+            }
+            _ => VoiceDecision::Drop,
+        }
+    }
+
+    pub fn add_voice(&mut self, entity: Entity, key: SfxKey, priority: f32, now_seconds: f32) {
+        self.voices.push(ActiveVoice {
+            entity,
+            key,
+            priority,
+            started_seconds: now_seconds,
+        });
+        self.last_started_seconds.insert(key, now_seconds);
+    }
+
+    /// The lowest priority voice matching `filter`, preferring the oldest on ties.
+    fn weakest_voice(&self, filter: impl Fn(&ActiveVoice) -> bool) -> Option<usize> {
+        self.voices
+            .iter()
+            .enumerate()
+            .filter(|(_, voice)| filter(voice))
+            .min_by(|(_, a), (_, b)| {
+                a.priority
+                    .total_cmp(&b.priority)
+                    .then(a.started_seconds.total_cmp(&b.started_seconds))
+            })
+            .map(|(index, _)| index)
+    }
+}
+
+/// Priority of a sound, given where in the world it came from, if anywhere.
+pub fn voice_priority(
+    key: SfxKey,
+    position: Option<Vec3>,
+    camera: Option<(&Camera, &GlobalTransform)>,
+) -> f32 {
+    let priority = voice_limit(key).priority;
+
+    let Some(position) = position else {
+        // sounds that don't come from the world, like UI feedback, are always "visible"
+        return priority + VISIBLE_PRIORITY_BONUS;
+    };
+
+    let visible = camera.is_some_and(|(camera, camera_transform)| {
+        is_in_viewport(camera, camera_transform, position)
+    });
+
+    if visible {
+        priority + VISIBLE_PRIORITY_BONUS
+    } else {
+        priority
+    }
+}
+
+fn is_in_viewport(camera: &Camera, camera_transform: &GlobalTransform, position: Vec3) -> bool {
+    let (Some(viewport_position), Some(viewport_size)) = (
+        camera.world_to_viewport(camera_transform, position),
+        camera.logical_viewport_size(),
+    ) else {
+        return false;
+    };
+
+    Rect::from_corners(Vec2::ZERO, viewport_size).contains(viewport_position)
+}
+
+/// Sounds despawn themselves when they finish, so drop them from the voice list once they're gone.
+fn forget_finished_voices(
+    mut voice_manager: ResMut<VoiceManager>,
+    q_audio: Query<(), With<Handle<AudioSource>>>,
+) {
+    voice_manager
+        .voices
+        .retain(|voice| q_audio.contains(voice.entity));
+}
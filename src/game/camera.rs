@@ -1,15 +1,20 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
-use bevy::input::{keyboard::KeyCode, mouse::{MouseButton, MouseWheel, MouseMotion}, ButtonInput};
+use bevy::input::{keyboard::KeyCode, mouse::{MouseButton, MouseScrollUnit, MouseWheel, MouseMotion}, ButtonInput};
 use bevy::window::{CursorGrabMode, PrimaryWindow};
 use bevy_mod_picking::*;
-use bevy_tweening::TweenCompleted;
-use events::{Drag, DragEnd, DragStart, Pointer};
+use bevy_tweening::{Animator, EaseFunction, Lens, Targetable, Tween, TweenCompleted};
+use events::{Click, Drag, DragEnd, DragStart, Pointer};
 use pointer::PointerButton;
 use prelude::ListenerInput;
 
+use crate::game::settings::Settings;
+use crate::game::spawn::level::{get_ring_grid_coordinates, get_ring_world_position, RingIndex, RING_QUAD_DIMENSIONS};
 use crate::screen::title::Background;
-use crate::screen::Screen;
-use crate::ui::shop::{AddRingUpgrade, UpgradeHistory, UpgradeKind};
+use crate::screen::{InRun, Screen};
+use crate::ui::event_log::EventLogPanel;
+use crate::ui::shop::{AddRingUpgrade, ShopScrollArea, UpgradeHistory, UpgradeKind};
 
 pub struct CameraControlPlugin;
 
@@ -18,21 +23,274 @@ impl Plugin for CameraControlPlugin {
         app.add_systems(
             Update,
             (
-                zoom_keyboard_input,
-                zoom_mouse_scroll,
+                (
+                    zoom_keyboard_input,
+                    zoom_mouse_scroll,
+                    smooth_zoom,
+                ).chain(),
                 move_camera_keyboard_input,
                 (
                     camera_drag_start,
                     camera_drag_end,
                     move_camera_mouse
-                ).chain()
+                ).chain(),
+                focus_ring_on_double_click,
+                fit_all_rings_key_input,
+                camera_bookmark_key_input,
             ).run_if(in_state(Screen::Playing))
         );
 
+        app.add_systems(
+            PostUpdate,
+            clamp_camera_to_rings
+                .before(TransformSystem::TransformPropagate)
+                .run_if(in_state(Screen::Playing))
+        );
+
+        app.add_systems(OnExit(InRun), reset_camera);
+
+        app.init_resource::<CameraBookmarks>();
+        app.init_resource::<CameraZoom>();
         app.observe(on_disable_disable_zoom);
     }
 }
 
+/// A new run starts on its first ring, without the last run's zoom or bookmarks.
+fn reset_camera(
+    mut commands: Commands,
+    q_camera: Query<Entity, With<Camera>>,
+    mut zoom: ResMut<CameraZoom>,
+    mut bookmarks: ResMut<CameraBookmarks>,
+) {
+    for camera_entity in &q_camera {
+        commands
+            .entity(camera_entity)
+            .remove::<Animator<Transform>>()
+            .insert(Camera2dBundle::default().transform);
+    }
+
+    *zoom = CameraZoom::default();
+    *bookmarks = CameraBookmarks::default();
+}
+
+/// The camera can't be moved until the player buys their second ring.
+pub fn camera_unlocked(upgrade_history: &UpgradeHistory) -> bool {
+    upgrade_history.history.contains(&UpgradeKind::AddRing(AddRingUpgrade {level: 1u32}))
+}
+
+/// The camera's zoom when it's focused on a single ring.
+pub const FOCUS_SCALE: f32 = 1.;
+const FOCUS_TWEEN_SECONDS: f32 = 0.6;
+const DOUBLE_CLICK_SECONDS: f32 = 0.35;
+
+/// How far past the outermost rings the camera is allowed to wander, in rings.
+const PAN_MARGIN_RINGS: f32 = 1.;
+
+/// Tweens both where the camera is and how far it's zoomed out.
+struct CameraFocusLens {
+    start_translation: Vec2,
+    end_translation: Vec2,
+    start_scale: f32,
+    end_scale: f32,
+}
+
+impl Lens<Transform> for CameraFocusLens {
+    fn lerp(&mut self, target: &mut dyn Targetable<Transform>, ratio: f32) {
+        let translation = self.start_translation.lerp(self.end_translation, ratio);
+        target.translation = translation.extend(target.translation.z);
+        target.scale = Vec3::splat(self.start_scale.lerp(self.end_scale, ratio));
+    }
+}
+
+/// Move the camera to look at `translation` at `scale`, smoothly unless the player asked for less motion.
+pub fn move_camera_to(
+    commands: &mut Commands,
+    camera_entity: Entity,
+    camera_transform: &Transform,
+    translation: Vec2,
+    scale: f32,
+    reduce_motion: bool,
+) {
+    if reduce_motion {
+        commands.entity(camera_entity).insert(Transform {
+            translation: translation.extend(camera_transform.translation.z),
+            scale: Vec3::splat(scale),
+            ..*camera_transform
+        });
+        return;
+    }
+
+    let tween = Tween::new(
+        EaseFunction::QuadraticInOut,
+        Duration::from_secs_f32(FOCUS_TWEEN_SECONDS),
+        CameraFocusLens {
+            start_translation: camera_transform.translation.xy(),
+            end_translation: translation,
+            start_scale: camera_transform.scale.x,
+            end_scale: scale,
+        },
+    );
+
+    commands.entity(camera_entity).insert(Animator::new(tween));
+}
+
+/// The world space area covered by every ring that exists.
+pub fn ring_bounds(ring_index: &RingIndex) -> Rect {
+    let half_ring = RING_QUAD_DIMENSIONS / 2.;
+
+    ring_index
+        .rings
+        .keys()
+        .map(|coords| {
+            let center = get_ring_world_position(*coords).xy();
+            Rect::from_corners(center - half_ring, center + half_ring)
+        })
+        .reduce(|a, b| a.union(b))
+        .unwrap_or(Rect::from_center_size(Vec2::ZERO, RING_QUAD_DIMENSIONS))
+}
+
+/// The zoom at which all of `bounds` fits in the viewport.
+fn scale_to_fit(bounds: Rect, viewport_size: Vec2) -> f32 {
+    (bounds.size() / viewport_size).max_element().max(FOCUS_SCALE)
+}
+
+fn focus_ring_on_double_click(
+    mut commands: Commands,
+    mut ev_click: EventReader<Pointer<Click>>,
+    q_bg: Query<Entity, With<Background>>,
+    q_camera: Query<(Entity, &Camera, &GlobalTransform, &Transform)>,
+    ring_index: Res<RingIndex>,
+    upgrade_history: Res<UpgradeHistory>,
+    settings: Res<Settings>,
+    time: Res<Time<Real>>,
+    mut last_click: Local<Option<(IVec2, f32)>>,
+) {
+    if !camera_unlocked(&upgrade_history) {
+        return;
+    }
+
+    let bg_entity = q_bg.single();
+    let (camera_entity, camera, camera_global_transform, camera_transform) = q_camera.single();
+
+    for click in ev_click.read() {
+        if click.event.button != PointerButton::Primary || click.target != bg_entity {
+            continue;
+        }
+
+        let Some(world_position) = camera.viewport_to_world_2d(camera_global_transform, click.pointer_location.position) else {
+            continue;
+        };
+
+        let coords = get_ring_grid_coordinates(world_position);
+        let ring_center = get_ring_world_position(coords).xy();
+
+        if !ring_index.rings.contains_key(&coords) || ring_center.distance(world_position) > RING_QUAD_DIMENSIONS.x / 2. {
+            *last_click = None;
+            continue;
+        }
+
+        let now = time.elapsed_seconds();
+
+        match *last_click {
+            Some((last_coords, last_seconds)) if last_coords == coords && now - last_seconds <= DOUBLE_CLICK_SECONDS => {
+                move_camera_to(&mut commands, camera_entity, camera_transform, ring_center, FOCUS_SCALE, settings.reduce_motion);
+                *last_click = None;
+            }
+            _ => *last_click = Some((coords, now)),
+        }
+    }
+}
+
+fn fit_all_rings_key_input(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    q_camera: Query<(Entity, &Camera, &Transform)>,
+    ring_index: Res<RingIndex>,
+    upgrade_history: Res<UpgradeHistory>,
+    settings: Res<Settings>,
+) {
+    if !camera_unlocked(&upgrade_history) || !keys.just_pressed(KeyCode::KeyF) {
+        return;
+    }
+
+    let (camera_entity, camera, camera_transform) = q_camera.single();
+    let Some(viewport_size) = camera.logical_viewport_size() else {
+        return;
+    };
+
+    let bounds = ring_bounds(&ring_index);
+    move_camera_to(&mut commands, camera_entity, camera_transform, bounds.center(), scale_to_fit(bounds, viewport_size), settings.reduce_motion);
+}
+
+const BOOKMARK_KEYS: [KeyCode; 9] = [
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
+    KeyCode::F9,
+];
+
+/// Saved camera positions and zoom levels. Ctrl + F1-F9 saves one, F1-F9 brings it back.
+#[derive(Resource, Default)]
+pub struct CameraBookmarks {
+    pub slots: [Option<(Vec2, f32)>; 9],
+}
+
+fn camera_bookmark_key_input(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    q_camera: Query<(Entity, &Transform), With<Camera>>,
+    mut bookmarks: ResMut<CameraBookmarks>,
+    upgrade_history: Res<UpgradeHistory>,
+    settings: Res<Settings>,
+) {
+    if !camera_unlocked(&upgrade_history) {
+        return;
+    }
+
+    let Some(slot) = BOOKMARK_KEYS.iter().position(|key| keys.just_pressed(*key)) else {
+        return;
+    };
+
+    let (camera_entity, camera_transform) = q_camera.single();
+
+    if keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        bookmarks.slots[slot] = Some((camera_transform.translation.xy(), camera_transform.scale.x));
+    } else if let Some((translation, scale)) = bookmarks.slots[slot] {
+        move_camera_to(&mut commands, camera_entity, camera_transform, translation, scale, settings.reduce_motion);
+    }
+}
+
+/// Keep the camera over the rings, and don't let it zoom out much further than it takes to see all of them.
+fn clamp_camera_to_rings(
+    mut q_camera: Query<(&Camera, &mut Transform)>,
+    ring_index: Res<RingIndex>,
+    zoom: Res<CameraZoom>,
+) {
+    let Ok((camera, mut transform)) = q_camera.get_single_mut() else {
+        return;
+    };
+    let Some(viewport_size) = camera.logical_viewport_size() else {
+        return;
+    };
+
+    let bounds = ring_bounds(&ring_index);
+    let (min_scale, max_scale) = zoom.limits(&ring_index, viewport_size);
+    let pan_bounds = bounds.inflate(RING_QUAD_DIMENSIONS.x * PAN_MARGIN_RINGS);
+
+    let scale = transform.scale.x.clamp(min_scale, max_scale);
+    let translation = transform.translation.xy().clamp(pan_bounds.min, pan_bounds.max);
+
+    if scale != transform.scale.x || translation != transform.translation.xy() {
+        transform.scale = Vec3::splat(scale);
+        transform.translation = translation.extend(transform.translation.z);
+    }
+}
+
 fn move_camera_mouse(
     mut ev_drag: EventReader<Pointer<Drag>>,
     q_bg: Query<Entity, With<Background>>,
@@ -116,55 +374,171 @@ fn on_disable_disable_zoom(
     }
 }
 
+/// How many pixels of a pixel based scroll (trackpads, browsers) count as one notch of a mouse wheel.
+const SCROLL_PIXELS_PER_LINE: f32 = 100.;
+/// How much one notch of the mouse wheel zooms.
+const SCROLL_ZOOM_FACTOR: f32 = 1.1;
+/// How much holding a zoom key zooms over a second.
+const KEYBOARD_ZOOM_FACTOR_PER_SECOND: f32 = 3.;
+/// How quickly the camera catches up to the zoom it's heading for. Higher is snappier.
+const ZOOM_SMOOTHING: f32 = 12.;
+
+/// Where the camera's zoom is headed, and how far it's allowed to go.
+#[derive(Resource)]
+pub struct CameraZoom {
+    pub min_scale: f32,
+    pub max_scale: f32,
+    target_scale: Option<f32>,
+    /// The point that stays put on screen while zooming, as an offset in pixels from the center of the viewport.
+    anchor: Vec2,
+}
+
+impl Default for CameraZoom {
+    fn default() -> Self {
+        Self {
+            min_scale: 1.,
+            max_scale: 64.,
+            target_scale: None,
+            anchor: Vec2::ZERO,
+        }
+    }
+}
+
+impl CameraZoom {
+    /// The zoom range allowed right now. On top of the configured limits,
+    /// the camera can't zoom out much further than it takes to see every ring.
+    pub fn limits(&self, ring_index: &RingIndex, viewport_size: Vec2) -> (f32, f32) {
+        // the first ring upgrade zooms out to 2x on its own, so always leave at least that much room
+        let rings_max_scale = scale_to_fit(ring_bounds(ring_index), viewport_size).max(2.) * 1.5;
+
+        (self.min_scale, rings_max_scale.min(self.max_scale).max(self.min_scale))
+    }
+
+    fn zoom_by(&mut self, factor: f32, current_scale: f32, anchor: Vec2, limits: (f32, f32)) {
+        let target = self.target_scale.unwrap_or(current_scale) * factor;
+        self.target_scale = Some(target.clamp(limits.0, limits.1));
+        self.anchor = anchor;
+    }
+}
+
 // System to handle keyboard input for zooming
 fn zoom_keyboard_input(
+    time: Res<Time<Real>>,
     keys: Res<ButtonInput<KeyCode>>,
-    mut query: Query<&mut Transform, (With<Camera>, Without<DisableZoom>)>,
+    query: Query<(&Camera, &Transform), Without<DisableZoom>>,
+    mut zoom: ResMut<CameraZoom>,
+    ring_index: Res<RingIndex>,
     upgrade_history: Res<UpgradeHistory>,
 ) {
-    if !upgrade_history.history.contains(&UpgradeKind::AddRing(AddRingUpgrade {level: 1u32})) {
+    if !camera_unlocked(&upgrade_history) {
         return;
     }
 
-    let mut zoom = 1.0;
-    const ZOOM_SPEED: f32 = 0.02;
+    let mut direction = 0.;
 
     if keys.pressed(KeyCode::KeyE) {
-        zoom -= ZOOM_SPEED;
+        direction -= 1.;
     }
     if keys.pressed(KeyCode::KeyQ) {
-        zoom += ZOOM_SPEED;
+        direction += 1.;
     }
 
-    for mut transform in query.iter_mut() {
-        transform.scale = (transform.scale * Vec3::splat(zoom)).max(Vec3::ONE);
+    if direction == 0. {
+        return;
     }
+
+    let Ok((camera, transform)) = query.get_single() else {
+        return;
+    };
+    let Some(viewport_size) = camera.logical_viewport_size() else {
+        return;
+    };
+
+    let factor = KEYBOARD_ZOOM_FACTOR_PER_SECOND.powf(direction * time.delta_seconds());
+    let limits = zoom.limits(&ring_index, viewport_size);
+    zoom.zoom_by(factor, transform.scale.x, Vec2::ZERO, limits);
 }
 
 fn zoom_mouse_scroll(
     mut mouse_wheel_events: EventReader<MouseWheel>,
-    mut query: Query<&mut Transform, (With<Camera>, Without<DisableZoom>)>,
+    query: Query<(&Camera, &Transform), Without<DisableZoom>>,
+    q_windows: Query<&Window, With<PrimaryWindow>>,
+    mut zoom: ResMut<CameraZoom>,
+    ring_index: Res<RingIndex>,
     upgrade_history: Res<UpgradeHistory>,
+    q_scroll_panels: Query<&Interaction, Or<(With<EventLogPanel>, With<ShopScrollArea>)>>,
 ) {
-    if !upgrade_history.history.contains(&UpgradeKind::AddRing(AddRingUpgrade {level: 1u32})) {
+    // the event log and the shop scroll instead while they're hovered
+    if !camera_unlocked(&upgrade_history) || q_scroll_panels.iter().any(|interaction| *interaction != Interaction::None) {
+        mouse_wheel_events.clear();
         return;
     }
 
-    let mut zoom = 1.0;
+    let lines: f32 = mouse_wheel_events
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / SCROLL_PIXELS_PER_LINE,
+        })
+        .sum();
 
-    #[cfg(not(target_family = "wasm"))]
-    const SCROLL_ZOOM_SPEED: f32 = 0.1;
+    if lines == 0. {
+        return;
+    }
 
-    #[cfg(target_family = "wasm")]
-    const SCROLL_ZOOM_SPEED: f32 = 0.0005;
+    let Ok((camera, transform)) = query.get_single() else {
+        return;
+    };
+    let Some(viewport_size) = camera.logical_viewport_size() else {
+        return;
+    };
+
+    // zoom toward whatever is under the cursor
+    let anchor = q_windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .map(|cursor| {
+            let offset = cursor - viewport_size / 2.;
+            Vec2::new(offset.x, -offset.y)
+        })
+        .unwrap_or(Vec2::ZERO);
+
+    let limits = zoom.limits(&ring_index, viewport_size);
+    zoom.zoom_by(SCROLL_ZOOM_FACTOR.powf(-lines), transform.scale.x, anchor, limits);
+}
 
-    for event in mouse_wheel_events.read() {
-        zoom -= event.y * SCROLL_ZOOM_SPEED;
+/// Eases the camera toward its target zoom, moving it so the anchor point stays under the cursor.
+fn smooth_zoom(
+    time: Res<Time<Real>>,
+    mut zoom: ResMut<CameraZoom>,
+    mut query: Query<(&mut Transform, Option<&Animator<Transform>>), (With<Camera>, Without<DisableZoom>)>,
+) {
+    let Some(target_scale) = zoom.target_scale else {
+        return;
+    };
+
+    let Ok((mut transform, animator)) = query.get_single_mut() else {
+        return;
+    };
+
+    // a focus tween owns the camera until it finishes
+    if animator.is_some_and(|animator| animator.tweenable().progress() < 1.) {
+        zoom.target_scale = None;
+        return;
     }
 
-    for mut transform in query.iter_mut() {
-        transform.scale = (transform.scale * Vec3::splat(zoom)).max(Vec3::ONE);
+    let current_scale = transform.scale.x;
+    let mut new_scale = current_scale.lerp(target_scale, 1. - (-ZOOM_SMOOTHING * time.delta_seconds()).exp());
+
+    if (new_scale - target_scale).abs() <= target_scale * 0.001 {
+        new_scale = target_scale;
+        zoom.target_scale = None;
     }
+
+    let anchor_world = transform.translation.xy() + zoom.anchor * current_scale;
+    transform.translation = (anchor_world - zoom.anchor * new_scale).extend(transform.translation.z);
+    transform.scale = Vec3::splat(new_scale);
 }
 
 fn move_camera_keyboard_input(
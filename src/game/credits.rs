@@ -0,0 +1,57 @@
+//! Who made the game and what it's built with, read from `assets/credits.ron`.
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+};
+use serde::Deserialize;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<Credits>();
+    app.init_asset_loader::<CreditsLoader>();
+}
+
+#[derive(Asset, TypePath, Deserialize)]
+pub struct Credits {
+    pub sections: Vec<CreditsSection>,
+}
+
+#[derive(Deserialize)]
+pub struct CreditsSection {
+    pub title: String,
+    pub entries: Vec<CreditsEntry>,
+}
+
+#[derive(Deserialize)]
+pub struct CreditsEntry {
+    /// What they did, or what the asset is.
+    pub role: String,
+    pub names: Vec<String>,
+    #[serde(default)]
+    pub license: Option<String>,
+}
+
+#[derive(Default)]
+struct CreditsLoader;
+
+impl AssetLoader for CreditsLoader {
+    type Asset = Credits;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Credits, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+}
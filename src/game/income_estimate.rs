@@ -0,0 +1,325 @@
+//! A rough simulation of how much the rings earn, used to preview what an upgrade is worth.
+//!
+//! It plays a few cycles of every ring the same way `on_socket_triggered` does: the hand
+//! passes each socket in turn, cooldowns are respected, RED sets off its neighbours, ORANGE
+//! shortens cooldowns and PINK builds the cycle multiplier. Things that depend on timing
+//! between rings, like BLUE's tier 3 soft triggers and cycle bonuses, are left out.
+
+use num_bigint::BigUint;
+
+use crate::ui::shop::{EnhanceColorUpgrade, UpgradeKind};
+
+use super::{
+    multiplier::Multiplier,
+    spawn::level::{map_socket_color_trigger_duration, SocketColor},
+};
+
+/// Cycles played before averaging, so effects that build up over cycles have time to show.
+const WARMUP_CYCLES: usize = 2;
+const SIMULATED_CYCLES: usize = 8;
+
+/// What the simulation needs to know about a ring.
+#[derive(Clone)]
+pub struct RingLayout {
+    pub sockets: Vec<SocketColor>,
+    pub cycle_duration: f32,
+}
+
+/// Roughly how much all the rings together pay out per cycle.
+pub fn estimate_income_per_cycle(
+    rings: &[RingLayout],
+    has_upgrade: impl Fn(UpgradeKind) -> bool,
+    payout_multiplier: &Multiplier,
+) -> BigUint {
+    let enhanced = |color: SocketColor, tier: u32| {
+        has_upgrade(UpgradeKind::EnhanceColor(EnhanceColorUpgrade {
+            color,
+            tier,
+        }))
+    };
+
+    let tiers = Tiers {
+        blue: (1..=3)
+            .rev()
+            .find(|tier| enhanced(SocketColor::BLUE, *tier))
+            .unwrap_or(0),
+        red: enhanced(SocketColor::RED, 1),
+        green: enhanced(SocketColor::GREEN, 1),
+        orange: enhanced(SocketColor::ORANGE, 1),
+    };
+
+    let blue_orb_count = rings
+        .iter()
+        .flat_map(|ring| &ring.sockets)
+        .filter(|color| **color == SocketColor::BLUE)
+        .count() as u32;
+
+    let mut simulations: Vec<RingSimulation> = rings
+        .iter()
+        .map(|ring| RingSimulation {
+            layout: ring,
+            last_triggered_seconds: vec![None; ring.sockets.len()],
+            previous_cycle_triggers: 0,
+        })
+        .collect();
+
+    let mut total = BigUint::ZERO;
+
+    for cycle in 0..WARMUP_CYCLES + SIMULATED_CYCLES {
+        let all_rings_previous_triggers: usize = simulations
+            .iter()
+            .map(|simulation| simulation.previous_cycle_triggers)
+            .sum();
+
+        for simulation in &mut simulations {
+            let payout =
+                simulation.play_cycle(cycle, &tiers, blue_orb_count, all_rings_previous_triggers);
+
+            if cycle >= WARMUP_CYCLES {
+                total += payout_multiplier.apply(&payout);
+            }
+        }
+    }
+
+    total / BigUint::from(SIMULATED_CYCLES)
+}
+
+struct Tiers {
+    blue: u32,
+    red: bool,
+    green: bool,
+    orange: bool,
+}
+
+struct RingSimulation<'a> {
+    layout: &'a RingLayout,
+    last_triggered_seconds: Vec<Option<f32>>,
+    previous_cycle_triggers: usize,
+}
+
+struct CycleState {
+    seconds: f32,
+    score: BigUint,
+    multiplier: Multiplier,
+    /// Sockets triggered this cycle, to spot GREEN retriggers.
+    triggered: Vec<bool>,
+    triggers: usize,
+}
+
+impl RingSimulation<'_> {
+    fn play_cycle(
+        &mut self,
+        cycle: usize,
+        tiers: &Tiers,
+        blue_orb_count: u32,
+        all_rings_previous_triggers: usize,
+    ) -> BigUint {
+        let socket_count = self.layout.sockets.len();
+        let mut state = CycleState {
+            seconds: 0.,
+            score: BigUint::ZERO,
+            multiplier: Multiplier::one(),
+            triggered: vec![false; socket_count],
+            triggers: 0,
+        };
+
+        // the hand passes socket 0 as the cycle starts, then goes round the other way
+        for step in 0..socket_count {
+            let socket = (socket_count - step) % socket_count;
+            state.seconds =
+                self.layout.cycle_duration * (cycle as f32 + step as f32 / socket_count as f32);
+
+            self.trigger(
+                socket,
+                Multiplier::one(),
+                None,
+                &mut state,
+                tiers,
+                blue_orb_count,
+                all_rings_previous_triggers,
+            );
+        }
+
+        self.previous_cycle_triggers = state.triggers;
+        state.multiplier.apply(&state.score)
+    }
+
+    fn trigger(
+        &mut self,
+        socket: usize,
+        bonus_factor: Multiplier,
+        red_source: Option<usize>,
+        state: &mut CycleState,
+        tiers: &Tiers,
+        blue_orb_count: u32,
+        all_rings_previous_triggers: usize,
+    ) {
+        let color = self.layout.sockets[socket];
+
+        if color == SocketColor::NONE {
+            return;
+        }
+
+        if let Some(last_triggered) = self.last_triggered_seconds[socket] {
+            if last_triggered + map_socket_color_trigger_duration(color) >= state.seconds {
+                return;
+            }
+        }
+
+        match color {
+            SocketColor::BLUE => {
+                let amount = match tiers.blue {
+                    3 => blue_orb_count * 6,
+                    2 => blue_orb_count * 4,
+                    1 => blue_orb_count * 2,
+                    _ => 1,
+                };
+                state.score += bonus_factor.apply(&BigUint::from(amount));
+            }
+            SocketColor::RED => {
+                if red_source != Some(socket) {
+                    let source = red_source.or(Some(socket));
+                    let factor = if tiers.red {
+                        &Multiplier::from_integer(2u32) * &bonus_factor
+                    } else {
+                        Multiplier::one()
+                    };
+                    let socket_count = self.layout.sockets.len();
+
+                    // it's on cooldown from here on, which is what stops chains going round forever
+                    self.last_triggered_seconds[socket] = Some(state.seconds);
+
+                    for neighbour in [
+                        (socket + socket_count - 1) % socket_count,
+                        (socket + 1) % socket_count,
+                    ] {
+                        self.trigger(
+                            neighbour,
+                            factor.clone(),
+                            source,
+                            state,
+                            tiers,
+                            blue_orb_count,
+                            all_rings_previous_triggers,
+                        );
+                    }
+                }
+            }
+            SocketColor::GREEN => {
+                let amount = if tiers.green {
+                    let retrigger_mult = if state.triggered[socket] { 5 } else { 1 };
+                    all_rings_previous_triggers * retrigger_mult
+                } else {
+                    self.previous_cycle_triggers
+                };
+                state.score += bonus_factor.apply(&BigUint::from(amount));
+            }
+            SocketColor::ORANGE => {
+                let amount = if tiers.orange { 1.0 } else { 0.5 } * bonus_factor.to_f32();
+
+                for (other, last_triggered) in self.last_triggered_seconds.iter_mut().enumerate() {
+                    if other != socket {
+                        if let Some(last_triggered) = last_triggered {
+                            *last_triggered -= amount;
+                        }
+                    }
+                }
+            }
+            SocketColor::PINK => state.multiplier += &bonus_factor,
+            SocketColor::NONE => {}
+        }
+
+        self.last_triggered_seconds[socket] = Some(state.seconds);
+        state.triggered[socket] = true;
+        state.triggers += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::spawn::level::{
+        SocketColor::{BLUE, NONE, PINK, RED},
+        RING_CYCLE_DURATION,
+    };
+
+    fn ring(sockets: &[SocketColor]) -> RingLayout {
+        RingLayout {
+            sockets: sockets.to_vec(),
+            cycle_duration: RING_CYCLE_DURATION,
+        }
+    }
+
+    fn enhanced(color: SocketColor, tier: u32) -> UpgradeKind {
+        UpgradeKind::EnhanceColor(EnhanceColorUpgrade { color, tier })
+    }
+
+    fn estimate(rings: &[RingLayout], upgrades: &[UpgradeKind]) -> BigUint {
+        estimate_income_per_cycle(rings, |kind| upgrades.contains(&kind), &Multiplier::one())
+    }
+
+    #[test]
+    fn blue_pays_more_per_orb_with_each_tier() {
+        let rings = [ring(&[BLUE, BLUE])];
+
+        assert_eq!(estimate(&rings, &[]), BigUint::from(2u32));
+        // two orbs, each paying 2, 4 and then 6 per BLUE orb on the board
+        assert_eq!(estimate(&rings, &[enhanced(BLUE, 1)]), BigUint::from(8u32));
+        assert_eq!(
+            estimate(&rings, &[enhanced(BLUE, 1), enhanced(BLUE, 2)]),
+            BigUint::from(16u32)
+        );
+        assert_eq!(
+            estimate(
+                &rings,
+                &[enhanced(BLUE, 1), enhanced(BLUE, 2), enhanced(BLUE, 3)]
+            ),
+            BigUint::from(24u32)
+        );
+    }
+
+    #[test]
+    fn blue_counts_orbs_on_every_ring() {
+        let rings = [ring(&[BLUE, NONE]), ring(&[BLUE, NONE])];
+
+        assert_eq!(estimate(&rings, &[enhanced(BLUE, 1)]), BigUint::from(8u32));
+    }
+
+    #[test]
+    fn red_sets_off_its_neighbours() {
+        // the hand sets off RED first, which sets off both BLUEs before the hand reaches them
+        let rings = [ring(&[RED, BLUE, BLUE])];
+
+        assert_eq!(estimate(&rings, &[]), BigUint::from(4u32));
+        // and once enhanced, what it sets off pays double
+        assert_eq!(estimate(&rings, &[enhanced(RED, 1)]), BigUint::from(6u32));
+    }
+
+    #[test]
+    fn red_chains_through_red() {
+        // the second RED passes the first one's bonus on, doubled again
+        let rings = [ring(&[RED, RED, BLUE, NONE])];
+
+        assert_eq!(estimate(&rings, &[]), BigUint::from(2u32));
+        assert_eq!(estimate(&rings, &[enhanced(RED, 1)]), BigUint::from(5u32));
+    }
+
+    #[test]
+    fn neighbours_on_cooldown_are_skipped() {
+        // the BLUE is on both sides of the RED, but can't go off twice in the same instant
+        let rings = [ring(&[RED, BLUE])];
+
+        assert_eq!(estimate(&rings, &[]), BigUint::from(2u32));
+    }
+
+    #[test]
+    fn pink_multiplies_the_cycles_it_triggers_in() {
+        let without_pink = [ring(&[NONE, BLUE, BLUE, BLUE, BLUE])];
+        let with_pink = [ring(&[PINK, BLUE, BLUE, BLUE, BLUE])];
+
+        assert_eq!(estimate(&without_pink, &[]), BigUint::from(4u32));
+        // PINK's cooldown only lets it go off every fourth cycle, doubling those from 4 to 8.
+        // Two of the eight averaged cycles get it: (6 * 4 + 2 * 8) / 8 = 5
+        assert_eq!(estimate(&with_pink, &[]), BigUint::from(5u32));
+    }
+}
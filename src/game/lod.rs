@@ -0,0 +1,183 @@
+//! Level of detail for rings.
+//!
+//! When the camera is zoomed far enough out that sockets are a few pixels wide, each ring is
+//! drawn as one flat quad colored by its income instead. This only changes what gets drawn;
+//! every ring keeps cycling and triggering exactly the same way whether it's visible or not.
+//!
+//! Rings and sockets animate off the shared global time rather than per-frame material writes,
+//! so rings that are off screen cost nothing beyond what Bevy's own culling already skips.
+
+use bevy::{
+    color::palettes::tailwind::{GRAY_600, GREEN_400},
+    prelude::*,
+    sprite::MaterialMesh2dBundle,
+};
+
+use crate::screen::Screen;
+
+use super::spawn::level::{GameplayMeshes, Ring};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<IncomeMaterials>();
+
+    app.add_systems(
+        Update,
+        (
+            add_ring_lod,
+            update_ring_lod,
+            color_simplified_rings,
+            despawn_orphaned_simplified_rings,
+        )
+            .chain()
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+/// Past this zoom, rings are drawn as flat quads.
+const SIMPLIFIED_SCALE: f32 = 8.;
+
+/// How many shades the simplified quads are drawn in. They share materials, one per shade.
+const INCOME_SHADES: usize = 8;
+
+/// The color of a ring that earns `income` as much as the best ring, by orders of magnitude.
+pub fn income_color(income: f32) -> LinearRgba {
+    LinearRgba::from(GRAY_600).mix(&GREEN_400.into(), income.clamp(0., 1.))
+}
+
+/// How much a ring earned last cycle compared to the best ring, from 0 to 1.
+/// Payouts span so many orders of magnitude that they're compared by bit length.
+pub fn income_fraction(ring: &Ring, max_bits: u64) -> f32 {
+    ring.previous_cycle_score.bits() as f32 / max_bits.max(1) as f32
+}
+
+#[derive(Component)]
+pub struct RingLod {
+    pub simplified: bool,
+    simplified_quad: Entity,
+}
+
+#[derive(Component)]
+struct SimplifiedRing {
+    ring: Entity,
+    shade: usize,
+}
+
+#[derive(Resource)]
+struct IncomeMaterials(Vec<Handle<ColorMaterial>>);
+
+impl FromWorld for IncomeMaterials {
+    fn from_world(world: &mut World) -> Self {
+        let mut materials = world.resource_mut::<Assets<ColorMaterial>>();
+
+        Self(
+            (0..INCOME_SHADES)
+                .map(|shade| {
+                    let income = shade as f32 / (INCOME_SHADES - 1) as f32;
+                    materials.add(ColorMaterial::from_color(income_color(income)))
+                })
+                .collect(),
+        )
+    }
+}
+
+fn add_ring_lod(
+    mut commands: Commands,
+    q_new_rings: Query<(Entity, &Transform), Added<Ring>>,
+    gameplay_meshes: Res<GameplayMeshes>,
+    income_materials: Res<IncomeMaterials>,
+) {
+    for (ring_entity, ring_transform) in &q_new_rings {
+        let simplified_quad = commands
+            .spawn((
+                Name::new("Simplified Ring"),
+                MaterialMesh2dBundle {
+                    mesh: gameplay_meshes.quad512.clone(),
+                    material: income_materials.0[0].clone(),
+                    transform: *ring_transform,
+                    visibility: Visibility::Hidden,
+                    ..default()
+                },
+                SimplifiedRing {
+                    ring: ring_entity,
+                    shade: 0,
+                },
+            ))
+            .id();
+
+        commands.entity(ring_entity).insert(RingLod {
+            simplified: false,
+            simplified_quad,
+        });
+    }
+}
+
+fn update_ring_lod(
+    q_camera: Query<&Transform, (With<Camera>, Without<Ring>)>,
+    mut q_rings: Query<(&mut RingLod, &mut Visibility), With<Ring>>,
+    mut q_simplified: Query<&mut Visibility, (With<SimplifiedRing>, Without<Ring>)>,
+) {
+    let Ok(camera_transform) = q_camera.get_single() else {
+        return;
+    };
+
+    let simplified = camera_transform.scale.x >= SIMPLIFIED_SCALE;
+
+    let (ring_visibility, quad_visibility) = if simplified {
+        (Visibility::Hidden, Visibility::Inherited)
+    } else {
+        (Visibility::Inherited, Visibility::Hidden)
+    };
+
+    for (mut lod, mut visibility) in &mut q_rings {
+        if lod.simplified != simplified {
+            lod.simplified = simplified;
+        }
+
+        visibility.set_if_neq(ring_visibility);
+
+        if let Ok(mut quad) = q_simplified.get_mut(lod.simplified_quad) {
+            quad.set_if_neq(quad_visibility);
+        }
+    }
+}
+
+fn color_simplified_rings(
+    q_rings: Query<&Ring>,
+    mut q_simplified: Query<(&mut SimplifiedRing, &mut Handle<ColorMaterial>, &ViewVisibility)>,
+    income_materials: Res<IncomeMaterials>,
+) {
+    let max_bits = q_rings
+        .iter()
+        .map(|ring| ring.previous_cycle_score.bits())
+        .max()
+        .unwrap_or(0);
+
+    for (mut simplified, mut material, view_visibility) in &mut q_simplified {
+        if !view_visibility.get() {
+            continue;
+        }
+
+        let Ok(ring) = q_rings.get(simplified.ring) else {
+            continue;
+        };
+
+        let shade = (income_fraction(ring, max_bits) * (INCOME_SHADES - 1) as f32).round() as usize;
+
+        if simplified.shade != shade {
+            simplified.shade = shade;
+            *material = income_materials.0[shade].clone();
+        }
+    }
+}
+
+fn despawn_orphaned_simplified_rings(
+    mut commands: Commands,
+    q_simplified: Query<(Entity, &SimplifiedRing)>,
+    q_rings: Query<(), With<Ring>>,
+) {
+    for (entity, simplified) in &q_simplified {
+        if !q_rings.contains(simplified.ring) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! A single 1×1 texture holding the game's elapsed time, bound into every material that animates.
+//!
+//! `globals.time` wraps in the shader, and copying the time into each material every frame
+//! dirtied every one of them. Instead, the render world writes the time into this one texture
+//! each frame, and shaders compare it against the timestamps their materials already carry.
+//!
+//! It's a texture rather than a uniform buffer because `unprepared_bind_group` is only handed the
+//! render world's images, so that's the one place a material can look up something shared.
+//!
+//! The time is split into whole seconds and the fraction of a second, so it stays precise long
+//! after an `f32` holding the total would have started rounding off the milliseconds.
+//! `shaders/global_time.wgsl` has the matching struct and helpers.
+
+use bevy::{
+    prelude::*,
+    render::{
+        render_asset::{RenderAssetUsages, RenderAssets},
+        render_resource::{
+            encase::{internal::WriteInto, UniformBuffer},
+            AsBindGroupError, BindGroupLayoutEntry, BindingType, BufferBindingType,
+            BufferInitDescriptor, BufferUsages, Extent3d, ImageDataLayout, OwnedBindingResource,
+            ShaderStages, ShaderType, TextureDimension, TextureFormat, TextureSampleType,
+            TextureViewDimension,
+        },
+        renderer::{RenderDevice, RenderQueue},
+        texture::GpuImage,
+        Extract, Render, RenderApp, RenderSet,
+    },
+};
+
+/// The texture every material binds. Its one texel is [`global_time_uniform`].
+pub const GLOBAL_TIME_IMAGE: Handle<Image> =
+    Handle::weak_from_u128(0x6b1f_37d2_a4c8_4e0b_9f5a_1d83_c2e6_7a90);
+
+pub(super) fn plugin(app: &mut App) {
+    app.world_mut().resource_mut::<Assets<Image>>().insert(
+        &GLOBAL_TIME_IMAGE,
+        Image::new_fill(
+            Extent3d::default(),
+            TextureDimension::D2,
+            &[0; 16],
+            TextureFormat::Rgba32Float,
+            RenderAssetUsages::RENDER_WORLD,
+        ),
+    );
+
+    let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+        return;
+    };
+
+    render_app
+        .init_resource::<GlobalTime>()
+        .add_systems(ExtractSchedule, extract_global_time)
+        .add_systems(
+            Render,
+            write_global_time.in_set(RenderSet::PrepareResources),
+        );
+}
+
+/// The time as the shaders see it: `[whole seconds, fraction of a second, padding, padding]`.
+pub fn global_time_uniform(elapsed_seconds: f64) -> Vec4 {
+    Vec4::new(
+        elapsed_seconds.trunc() as f32,
+        elapsed_seconds.fract() as f32,
+        0.,
+        0.,
+    )
+}
+
+/// This frame's [`global_time_uniform`], in the render world.
+#[derive(Resource, Default)]
+struct GlobalTime(Vec4);
+
+/// The binding a material's `unprepared_bind_group` should hand back for the global time.
+/// Until the texture has been prepared, the material has to wait for the next frame.
+pub fn global_time_binding(
+    binding: u32,
+    images: &RenderAssets<GpuImage>,
+) -> Result<(u32, OwnedBindingResource), AsBindGroupError> {
+    let image = images
+        .get(&GLOBAL_TIME_IMAGE)
+        .ok_or(AsBindGroupError::RetryNextUpdate)?;
+
+    Ok((
+        binding,
+        OwnedBindingResource::TextureView(image.texture_view.clone()),
+    ))
+}
+
+/// The layout entry a material's `bind_group_layout_entries` should include for the global time.
+pub fn global_time_layout_entry(binding: u32) -> BindGroupLayoutEntry {
+    BindGroupLayoutEntry {
+        binding,
+        visibility: ShaderStages::VERTEX_FRAGMENT,
+        ty: BindingType::Texture {
+            sample_type: TextureSampleType::Float { filterable: false },
+            view_dimension: TextureViewDimension::D2,
+            multisampled: false,
+        },
+        count: None,
+    }
+}
+
+/// A binding for a plain uniform value, the same as `#[uniform(binding)]` would generate.
+///
+/// This makes a new buffer on every call. Materials only prepare their bind groups again when
+/// they change, so that's fine there, but nothing that runs every frame should use it.
+pub fn uniform_binding<T: ShaderType + WriteInto>(
+    binding: u32,
+    value: &T,
+    render_device: &RenderDevice,
+) -> (u32, OwnedBindingResource) {
+    let mut buffer = UniformBuffer::new(Vec::new());
+    buffer.write(value).unwrap();
+
+    (
+        binding,
+        OwnedBindingResource::Buffer(render_device.create_buffer_with_data(
+            &BufferInitDescriptor {
+                label: None,
+                usage: BufferUsages::COPY_DST | BufferUsages::UNIFORM,
+                contents: buffer.as_ref(),
+            },
+        )),
+    )
+}
+
+pub fn uniform_layout_entry<T: ShaderType>(binding: u32) -> BindGroupLayoutEntry {
+    BindGroupLayoutEntry {
+        binding,
+        visibility: ShaderStages::all(),
+        ty: BindingType::Buffer {
+            ty: BufferBindingType::Uniform,
+            has_dynamic_offset: false,
+            min_binding_size: Some(T::min_size()),
+        },
+        count: None,
+    }
+}
+
+fn extract_global_time(mut global_time: ResMut<GlobalTime>, time: Extract<Res<Time>>) {
+    global_time.0 = global_time_uniform(time.elapsed_seconds_f64());
+}
+
+fn write_global_time(
+    global_time: Res<GlobalTime>,
+    images: Res<RenderAssets<GpuImage>>,
+    render_queue: Res<RenderQueue>,
+) {
+    let Some(image) = images.get(&GLOBAL_TIME_IMAGE) else {
+        return;
+    };
+
+    let texel: Vec<u8> = global_time
+        .0
+        .to_array()
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect();
+
+    render_queue.write_texture(
+        image.texture.as_image_copy(),
+        &texel,
+        ImageDataLayout {
+            offset: 0,
+            bytes_per_row: Some(texel.len() as u32),
+            rows_per_image: None,
+        },
+        Extent3d::default(),
+    );
+}
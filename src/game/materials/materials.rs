@@ -1,13 +1,54 @@
 use bevy::{
     prelude::*,
-    render::render_resource::{AsBindGroup, ShaderRef},
+    render::{
+        render_asset::RenderAssets,
+        render_resource::{
+            AsBindGroup, AsBindGroupError, BindGroupLayout, BindGroupLayoutEntry, ShaderRef,
+            UnpreparedBindGroup,
+        },
+        renderer::RenderDevice,
+        texture::{FallbackImage, GpuImage},
+    },
     sprite::Material2d,
 };
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+use super::global_time::{
+    global_time_binding, global_time_layout_entry, uniform_binding, uniform_layout_entry,
+};
+
+/// A timestamp far enough in the past that anything animating from it has long finished.
+pub const LONG_AGO_SECONDS: f32 = -1e17;
+
+// These materials bind the shared global time texture alongside their own uniforms,
+// which `#[derive(AsBindGroup)]` has no way to express, so their bindings are written out by hand.
+
+#[derive(Asset, TypePath, Debug, Clone)]
 pub struct RingMaterial {
-    #[uniform(0)]
-    pub data: Vec4, // `radius`, `thickness` and padding
+    pub data: Vec4, // `radius`, `thickness`, cycle start seconds, cycle duration seconds
+}
+
+impl AsBindGroup for RingMaterial {
+    type Data = ();
+
+    fn unprepared_bind_group(
+        &self,
+        _layout: &BindGroupLayout,
+        render_device: &RenderDevice,
+        images: &RenderAssets<GpuImage>,
+        _fallback_image: &FallbackImage,
+    ) -> Result<UnpreparedBindGroup<Self::Data>, AsBindGroupError> {
+        Ok(UnpreparedBindGroup {
+            bindings: vec![
+                uniform_binding(0, &self.data, render_device),
+                global_time_binding(1, images)?,
+            ],
+            data: (),
+        })
+    }
+
+    fn bind_group_layout_entries(_render_device: &RenderDevice) -> Vec<BindGroupLayoutEntry> {
+        vec![uniform_layout_entry::<Vec4>(0), global_time_layout_entry(1)]
+    }
 }
 
 impl Material2d for RingMaterial {
@@ -16,22 +57,89 @@ impl Material2d for RingMaterial {
     }
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct HandMaterial {
+    pub data: Vec4, // `width`, `height`, cycle start seconds, cycle duration seconds
+    pub flash_color: LinearRgba,
+    pub flash: Vec4, // [flash start seconds, flash duration seconds, unused, unused]
+}
+
+impl AsBindGroup for HandMaterial {
+    type Data = ();
+
+    fn unprepared_bind_group(
+        &self,
+        _layout: &BindGroupLayout,
+        render_device: &RenderDevice,
+        images: &RenderAssets<GpuImage>,
+        _fallback_image: &FallbackImage,
+    ) -> Result<UnpreparedBindGroup<Self::Data>, AsBindGroupError> {
+        Ok(UnpreparedBindGroup {
+            bindings: vec![
+                uniform_binding(0, &self.data, render_device),
+                uniform_binding(1, &self.flash_color, render_device),
+                uniform_binding(2, &self.flash, render_device),
+                global_time_binding(3, images)?,
+            ],
+            data: (),
+        })
+    }
+
+    fn bind_group_layout_entries(_render_device: &RenderDevice) -> Vec<BindGroupLayoutEntry> {
+        vec![
+            uniform_layout_entry::<Vec4>(0),
+            uniform_layout_entry::<LinearRgba>(1),
+            uniform_layout_entry::<Vec4>(2),
+            global_time_layout_entry(3),
+        ]
+    }
+}
+
+impl Material2d for HandMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/hand.wgsl".into()
+    }
+}
+
+#[derive(Asset, TypePath, Debug, Clone)]
 pub struct SocketMaterial {
-    #[uniform(0)]
     pub inserted_color: LinearRgba,
-
-    #[uniform(1)]
     pub bevel_color: LinearRgba,
-
-    #[uniform(2)]
     pub highlight_color: LinearRgba,
+    pub data: Vec4, // [start time seconds, trigger_duration, unmodified start time seconds, shape]
+}
+
+impl AsBindGroup for SocketMaterial {
+    type Data = ();
 
-    #[uniform(3)]
-    pub data: Vec4, // [start time seconds, trigger_duration, padding, padding]
+    fn unprepared_bind_group(
+        &self,
+        _layout: &BindGroupLayout,
+        render_device: &RenderDevice,
+        images: &RenderAssets<GpuImage>,
+        _fallback_image: &FallbackImage,
+    ) -> Result<UnpreparedBindGroup<Self::Data>, AsBindGroupError> {
+        Ok(UnpreparedBindGroup {
+            bindings: vec![
+                uniform_binding(0, &self.inserted_color, render_device),
+                uniform_binding(1, &self.bevel_color, render_device),
+                uniform_binding(2, &self.highlight_color, render_device),
+                uniform_binding(3, &self.data, render_device),
+                global_time_binding(4, images)?,
+            ],
+            data: (),
+        })
+    }
 
-    #[uniform(4)]
-    pub data2: Vec4 // [time]
+    fn bind_group_layout_entries(_render_device: &RenderDevice) -> Vec<BindGroupLayoutEntry> {
+        vec![
+            uniform_layout_entry::<LinearRgba>(0),
+            uniform_layout_entry::<LinearRgba>(1),
+            uniform_layout_entry::<LinearRgba>(2),
+            uniform_layout_entry::<Vec4>(3),
+            global_time_layout_entry(4),
+        ]
+    }
 }
 
 impl Material2d for SocketMaterial {
@@ -1,13 +1,19 @@
+pub mod global_time;
 pub mod materials;
+pub mod socket_instances;
 
 use bevy::{prelude::*, sprite::Material2dPlugin};
-use materials::{BackgroundMaterial, RingMaterial, SocketMaterial, SocketUiMaterial};
+use materials::{BackgroundMaterial, HandMaterial, RingMaterial, SocketMaterial, SocketUiMaterial};
 
 pub fn plugin(app: &mut App) {
     app.add_plugins((
         Material2dPlugin::<RingMaterial>::default(),
+        Material2dPlugin::<HandMaterial>::default(),
         Material2dPlugin::<SocketMaterial>::default(),
         UiMaterialPlugin::<SocketUiMaterial>::default(),
         Material2dPlugin::<BackgroundMaterial>::default(),
     ));
+
+    app.add_plugins(global_time::plugin);
+    app.add_plugins(socket_instances::SocketInstancesPlugin);
 }
@@ -0,0 +1,417 @@
+//! Draws a whole batch of sockets in one draw call.
+//!
+//! An entity with [`SocketInstances`] gets all of its sockets drawn at once from a single
+//! per-instance vertex buffer, instead of one entity and one [`SocketMaterial`] per socket.
+//! Per-instance vertex attributes work on WebGL2, which storage buffers don't.
+//! The sockets look exactly like the material ones; both shaders share `shaders/socket_shapes.wgsl`.
+//!
+//! Only the cycle displays above the rings are drawn this way so far. The sockets on the rings
+//! are still one entity and one [`SocketMaterial`] each. Instancing them too means moving
+//! picking, hovering and tweening onto lightweight collider entities, and is left for its own
+//! follow-up change.
+//!
+//! [`SocketMaterial`]: super::materials::SocketMaterial
+
+use bevy::{
+    core_pipeline::core_2d::Transparent2d,
+    ecs::{
+        entity::EntityHashMap,
+        system::{
+            lifetimeless::{Read, SRes},
+            SystemParamItem,
+        },
+    },
+    math::FloatOrd,
+    prelude::*,
+    render::{
+        render_asset::RenderAssets,
+        render_phase::{
+            AddRenderCommand, DrawFunctions, PhaseItem, PhaseItemExtraIndex, RenderCommand,
+            RenderCommandResult, SetItemPipeline, TrackedRenderPass, ViewSortedRenderPhases,
+        },
+        render_resource::{
+            BindGroup, BindGroupEntries, BindGroupLayout, BlendState, Buffer, BufferInitDescriptor,
+            BufferUsages, ColorTargetState, ColorWrites, FragmentState, MultisampleState,
+            PipelineCache, PrimitiveState, RenderPipelineDescriptor, SpecializedRenderPipeline,
+            SpecializedRenderPipelines, TextureFormat, VertexAttribute, VertexBufferLayout,
+            VertexFormat, VertexState, VertexStepMode,
+        },
+        renderer::{RenderDevice, RenderQueue},
+        texture::{BevyDefault, GpuImage},
+        view::{ExtractedView, ViewTarget},
+        Extract, Render, RenderApp, RenderSet,
+    },
+    sprite::{Mesh2dPipeline, Mesh2dPipelineKey, SetMesh2dViewBindGroup},
+};
+
+use super::global_time::{global_time_layout_entry, GLOBAL_TIME_IMAGE};
+
+pub struct SocketInstancesPlugin;
+
+impl Plugin for SocketInstancesPlugin {
+    fn build(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .add_render_command::<Transparent2d, DrawSocketInstances>()
+            .init_resource::<SpecializedRenderPipelines<SocketInstancePipeline>>()
+            .init_resource::<SocketInstanceBufferCache>()
+            .add_systems(ExtractSchedule, extract_socket_instances)
+            .add_systems(
+                Render,
+                (
+                    queue_socket_instances.in_set(RenderSet::QueueMeshes),
+                    prepare_socket_instance_buffers.in_set(RenderSet::PrepareResources),
+                    prepare_socket_instance_time_bind_group.in_set(RenderSet::PrepareBindGroups),
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        // needs the 2d mesh pipeline, which only exists once its own plugin has finished
+        render_app.init_resource::<SocketInstancePipeline>();
+    }
+}
+
+/// One socket in a [`SocketInstances`] batch.
+#[derive(Clone, Copy, Debug)]
+pub struct SocketInstance {
+    /// Relative to the entity holding the batch.
+    pub position: Vec2,
+    pub size: f32,
+    pub inserted_color: LinearRgba,
+    pub bevel_color: LinearRgba,
+    pub highlight_color: LinearRgba,
+    pub data: Vec4, // [start time seconds, trigger_duration, unmodified start time seconds, shape]
+}
+
+/// Sockets drawn together in one draw call, on top of the entity's own transform.
+/// Needs a [`SpatialBundle`] to be drawn. An empty batch draws nothing.
+#[derive(Component, Clone, Default)]
+pub struct SocketInstances(pub Vec<SocketInstance>);
+
+/// How many floats one instance takes up in the vertex buffer: five `vec4`s.
+const INSTANCE_FLOATS: usize = 20;
+
+#[derive(Component)]
+struct ExtractedSocketInstances {
+    z: f32,
+    data: Vec<f32>,
+}
+
+#[derive(Component)]
+struct SocketInstanceBuffer {
+    buffer: Buffer,
+    length: u32,
+}
+
+/// Render world entities are cleared every frame, so the buffers are kept here between frames,
+/// keyed by the main world entity, along with the data they were last written with.
+#[derive(Resource, Default)]
+struct SocketInstanceBufferCache(EntityHashMap<CachedSocketInstanceBuffer>);
+
+struct CachedSocketInstanceBuffer {
+    buffer: Buffer,
+    data: Vec<f32>,
+}
+
+fn extract_socket_instances(
+    mut commands: Commands,
+    q_instances: Extract<
+        Query<(
+            Entity,
+            &SocketInstances,
+            &GlobalTransform,
+            &InheritedVisibility,
+        )>,
+    >,
+) {
+    let mut values = Vec::new();
+
+    for (entity, instances, global_transform, visibility) in &q_instances {
+        if !visibility.get() || instances.0.is_empty() {
+            continue;
+        }
+
+        let scale = global_transform.compute_transform().scale.x;
+        let mut data = Vec::with_capacity(instances.0.len() * INSTANCE_FLOATS);
+
+        for instance in &instances.0 {
+            let world_position = global_transform.transform_point(instance.position.extend(0.));
+
+            data.extend(world_position.extend(instance.size * scale).to_array());
+            data.extend(instance.inserted_color.to_f32_array());
+            data.extend(instance.bevel_color.to_f32_array());
+            data.extend(instance.highlight_color.to_f32_array());
+            data.extend(instance.data.to_array());
+        }
+
+        values.push((
+            entity,
+            ExtractedSocketInstances {
+                z: global_transform.translation().z,
+                data,
+            },
+        ));
+    }
+
+    commands.insert_or_spawn_batch(values);
+}
+
+fn prepare_socket_instance_buffers(
+    mut commands: Commands,
+    q_instances: Query<(Entity, &ExtractedSocketInstances)>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut cache: ResMut<SocketInstanceBufferCache>,
+) {
+    // batches that weren't extracted this frame are gone, or hidden
+    cache.0.retain(|entity, _| q_instances.contains(*entity));
+
+    for (entity, instances) in &q_instances {
+        let contents = || -> Vec<u8> {
+            instances
+                .data
+                .iter()
+                .flat_map(|value| value.to_ne_bytes())
+                .collect()
+        };
+
+        match cache.0.get_mut(&entity) {
+            Some(cached) if cached.data == instances.data => {}
+            // the batch only grows and shrinks with the cycle, so the old buffer usually still fits
+            Some(cached) if cached.data.len() >= instances.data.len() => {
+                render_queue.write_buffer(&cached.buffer, 0, &contents());
+                cached.data.clone_from(&instances.data);
+            }
+            _ => {
+                let buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+                    label: Some("socket_instance_buffer"),
+                    contents: &contents(),
+                    usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+                });
+
+                cache.0.insert(
+                    entity,
+                    CachedSocketInstanceBuffer {
+                        buffer,
+                        data: instances.data.clone(),
+                    },
+                );
+            }
+        }
+
+        commands.entity(entity).insert(SocketInstanceBuffer {
+            buffer: cache.0[&entity].buffer.clone(),
+            length: (instances.data.len() / INSTANCE_FLOATS) as u32,
+        });
+    }
+}
+
+#[derive(Resource)]
+struct SocketInstanceTimeBindGroup(BindGroup);
+
+/// The global time texture never changes, so its bind group only has to be made once it's been prepared.
+fn prepare_socket_instance_time_bind_group(
+    mut commands: Commands,
+    pipeline: Res<SocketInstancePipeline>,
+    render_device: Res<RenderDevice>,
+    images: Res<RenderAssets<GpuImage>>,
+    bind_group: Option<Res<SocketInstanceTimeBindGroup>>,
+) {
+    if bind_group.is_some() {
+        return;
+    }
+
+    let Some(global_time) = images.get(&GLOBAL_TIME_IMAGE) else {
+        return;
+    };
+
+    commands.insert_resource(SocketInstanceTimeBindGroup(
+        render_device.create_bind_group(
+            "socket_instance_time_bind_group",
+            &pipeline.time_layout,
+            &BindGroupEntries::single(&global_time.texture_view),
+        ),
+    ));
+}
+
+fn queue_socket_instances(
+    transparent_draw_functions: Res<DrawFunctions<Transparent2d>>,
+    pipeline: Res<SocketInstancePipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<SocketInstancePipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    msaa: Res<Msaa>,
+    q_instances: Query<(Entity, &ExtractedSocketInstances)>,
+    mut transparent_render_phases: ResMut<ViewSortedRenderPhases<Transparent2d>>,
+    views: Query<(Entity, &ExtractedView)>,
+) {
+    if q_instances.is_empty() {
+        return;
+    }
+
+    let draw_socket_instances = transparent_draw_functions
+        .read()
+        .id::<DrawSocketInstances>();
+
+    for (view_entity, view) in &views {
+        let Some(transparent_phase) = transparent_render_phases.get_mut(&view_entity) else {
+            continue;
+        };
+
+        let key = Mesh2dPipelineKey::from_msaa_samples(msaa.samples())
+            | Mesh2dPipelineKey::from_hdr(view.hdr);
+        let pipeline_id = pipelines.specialize(&pipeline_cache, &pipeline, key);
+
+        for (entity, instances) in &q_instances {
+            transparent_phase.add(Transparent2d {
+                entity,
+                draw_function: draw_socket_instances,
+                pipeline: pipeline_id,
+                sort_key: FloatOrd(instances.z),
+                batch_range: 0..1,
+                extra_index: PhaseItemExtraIndex::NONE,
+            });
+        }
+    }
+}
+
+#[derive(Resource)]
+struct SocketInstancePipeline {
+    shader: Handle<Shader>,
+    view_layout: BindGroupLayout,
+    time_layout: BindGroupLayout,
+}
+
+impl FromWorld for SocketInstancePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        Self {
+            shader: world
+                .resource::<AssetServer>()
+                .load("shaders/socket_instanced.wgsl"),
+            view_layout: world.resource::<Mesh2dPipeline>().view_layout.clone(),
+            time_layout: render_device.create_bind_group_layout(
+                "socket_instance_time_layout",
+                &[global_time_layout_entry(0)],
+            ),
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for SocketInstancePipeline {
+    type Key = Mesh2dPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let format = match key.contains(Mesh2dPipelineKey::HDR) {
+            true => ViewTarget::TEXTURE_FORMAT_HDR,
+            false => TextureFormat::bevy_default(),
+        };
+
+        // one `vec4` attribute per location, stepping once per socket
+        let attributes = (0..INSTANCE_FLOATS as u32 / 4)
+            .map(|location| VertexAttribute {
+                format: VertexFormat::Float32x4,
+                offset: location as u64 * VertexFormat::Float32x4.size(),
+                shader_location: location,
+            })
+            .collect();
+
+        RenderPipelineDescriptor {
+            label: Some("socket_instance_pipeline".into()),
+            layout: vec![self.view_layout.clone(), self.time_layout.clone()],
+            push_constant_ranges: vec![],
+            vertex: VertexState {
+                shader: self.shader.clone(),
+                shader_defs: vec![],
+                entry_point: "vertex".into(),
+                buffers: vec![VertexBufferLayout {
+                    array_stride: (INSTANCE_FLOATS * std::mem::size_of::<f32>()) as u64,
+                    step_mode: VertexStepMode::Instance,
+                    attributes,
+                }],
+            },
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState {
+                count: key.msaa_samples(),
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+        }
+    }
+}
+
+type DrawSocketInstances = (
+    SetItemPipeline,
+    SetMesh2dViewBindGroup<0>,
+    SetSocketInstanceTimeBindGroup<1>,
+    DrawSocketInstanced,
+);
+
+struct SetSocketInstanceTimeBindGroup<const I: usize>;
+
+impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetSocketInstanceTimeBindGroup<I> {
+    type Param = Option<SRes<SocketInstanceTimeBindGroup>>;
+    type ViewQuery = ();
+    type ItemQuery = ();
+
+    fn render<'w>(
+        _item: &P,
+        _view: (),
+        _entity: Option<()>,
+        bind_group: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        // not made until the global time texture has been prepared
+        let Some(bind_group) = bind_group else {
+            return RenderCommandResult::Failure;
+        };
+
+        pass.set_bind_group(I, &bind_group.into_inner().0, &[]);
+        RenderCommandResult::Success
+    }
+}
+
+struct DrawSocketInstanced;
+
+impl<P: PhaseItem> RenderCommand<P> for DrawSocketInstanced {
+    type Param = ();
+    type ViewQuery = ();
+    type ItemQuery = Read<SocketInstanceBuffer>;
+
+    fn render<'w>(
+        _item: &P,
+        _view: (),
+        instance_buffer: Option<&'w SocketInstanceBuffer>,
+        _param: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(instance_buffer) = instance_buffer else {
+            return RenderCommandResult::Failure;
+        };
+
+        // the quad's six corners come from the vertex index, so the only buffer is the instances
+        pass.set_vertex_buffer(0, instance_buffer.buffer.slice(..));
+        pass.draw(0..6, 0..instance_buffer.length);
+
+        RenderCommandResult::Success
+    }
+}
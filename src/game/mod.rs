@@ -2,17 +2,34 @@
 
 use bevy::prelude::*;
 
+pub mod achievements;
 pub mod assets;
 pub mod audio;
+pub mod income_estimate;
+pub mod lod;
 pub mod materials;
+pub mod multiplier;
+pub mod number_format;
+pub mod persistence;
+pub mod records;
+pub mod save;
+pub mod settings;
 pub mod spawn;
 pub mod camera;
+pub mod credits;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
         audio::plugin,
+        // the credits loader has to be there before `assets` asks for the file
+        credits::plugin,
         assets::plugin,
+        achievements::plugin,
+        lod::plugin,
         materials::plugin,
+        records::plugin,
+        save::plugin,
+        settings::plugin,
         spawn::plugin,
         camera::CameraControlPlugin,
     ));
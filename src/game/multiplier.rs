@@ -0,0 +1,395 @@
+//! Multipliers on payouts, like a ring's cycle multiplier and the bonus factor on chained triggers.
+//!
+//! They're fixed point decimals: a count of millionths, held in a `BigUint`. That keeps adding
+//! them exact however far PINK pushes them, and applying one to an amount of money exact past
+//! anything an `f32` or `f64` could hold. Only the rounding down of the final millionth is lost.
+
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Mul},
+};
+
+use num_bigint::BigUint;
+
+/// How many decimal places a multiplier keeps.
+pub const MULTIPLIER_DECIMALS: usize = 6;
+
+const SCALE: u32 = 1_000_000;
+
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Multiplier {
+    millionths: BigUint,
+}
+
+impl Multiplier {
+    pub fn one() -> Self {
+        Self::from_integer(1u32)
+    }
+
+    pub fn from_integer(value: impl Into<BigUint>) -> Self {
+        Self {
+            millionths: value.into() * SCALE,
+        }
+    }
+
+    pub fn from_millionths(millionths: impl Into<BigUint>) -> Self {
+        Self {
+            millionths: millionths.into(),
+        }
+    }
+
+    /// The nearest multiplier to `value`. Negative and non-finite values become zero.
+    pub fn from_f64(value: f64) -> Self {
+        let millionths = (value * SCALE as f64).round();
+
+        if !millionths.is_finite() || millionths <= 0. {
+            return Self::default();
+        }
+
+        let millionths = if millionths < u64::MAX as f64 {
+            BigUint::from(millionths as u64)
+        } else {
+            // too big for an integer type, but `f64` prints every digit it has
+            BigUint::parse_bytes(format!("{:.0}", millionths).as_bytes(), 10).unwrap_or_default()
+        };
+
+        Self { millionths }
+    }
+
+    /// `amount` times this multiplier, rounded down to a whole amount.
+    pub fn apply(&self, amount: &BigUint) -> BigUint {
+        amount * &self.millionths / SCALE
+    }
+
+    /// The whole part, with the fraction dropped.
+    pub fn integer_part(&self) -> BigUint {
+        &self.millionths / SCALE
+    }
+
+    /// The fraction as millionths, from 0 to 999,999.
+    pub fn fraction_millionths(&self) -> u32 {
+        u32::try_from(&(&self.millionths % SCALE)).unwrap_or(0)
+    }
+
+    /// As an `f32`, for things like cooldowns that are floats anyway. Saturates at `f32::MAX`.
+    pub fn to_f32(&self) -> f32 {
+        match u64::try_from(&self.millionths) {
+            Ok(millionths) => (millionths as f64 / SCALE as f64) as f32,
+            Err(_) => f32::MAX,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.millionths == BigUint::ZERO
+    }
+
+    /// How much bigger this is than `other`, or zero if it isn't.
+    pub fn saturating_sub(&self, other: &Self) -> Self {
+        if self.millionths > other.millionths {
+            Self {
+                millionths: &self.millionths - &other.millionths,
+            }
+        } else {
+            Self::default()
+        }
+    }
+}
+
+impl Add<&Multiplier> for &Multiplier {
+    type Output = Multiplier;
+
+    fn add(self, rhs: &Multiplier) -> Multiplier {
+        Multiplier {
+            millionths: &self.millionths + &rhs.millionths,
+        }
+    }
+}
+
+impl AddAssign<&Multiplier> for Multiplier {
+    fn add_assign(&mut self, rhs: &Multiplier) {
+        self.millionths += &rhs.millionths;
+    }
+}
+
+/// Rounds down to the nearest millionth.
+impl Mul<&Multiplier> for &Multiplier {
+    type Output = Multiplier;
+
+    fn mul(self, rhs: &Multiplier) -> Multiplier {
+        Multiplier {
+            millionths: &self.millionths * &rhs.millionths / SCALE,
+        }
+    }
+}
+
+/// Every digit, without trailing zeros: `2`, `2.5`, `0.000001`.
+impl fmt::Display for Multiplier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fraction = format!(
+            "{:0width$}",
+            self.fraction_millionths(),
+            width = MULTIPLIER_DECIMALS
+        );
+        let fraction = fraction.trim_end_matches('0');
+
+        if fraction.is_empty() {
+            write!(f, "{}", self.integer_part())
+        } else {
+            write!(f, "{}.{}", self.integer_part(), fraction)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+
+    /// The reference: an exact fraction, kept unreduced, with all rounding left to the very end.
+    #[derive(Clone)]
+    struct Fraction {
+        numerator: BigUint,
+        denominator: BigUint,
+    }
+
+    impl Fraction {
+        fn millionths(millionths: u64) -> Self {
+            Self {
+                numerator: BigUint::from(millionths),
+                denominator: BigUint::from(SCALE),
+            }
+        }
+
+        fn add(&self, other: &Self) -> Self {
+            Self {
+                numerator: &self.numerator * &other.denominator + &other.numerator * &self.denominator,
+                denominator: &self.denominator * &other.denominator,
+            }
+        }
+
+        fn mul(&self, other: &Self) -> Self {
+            Self {
+                numerator: &self.numerator * &other.numerator,
+                denominator: &self.denominator * &other.denominator,
+            }
+        }
+
+        fn floor_times(&self, amount: &BigUint) -> BigUint {
+            amount * &self.numerator / &self.denominator
+        }
+
+        fn floor_millionths(&self) -> BigUint {
+            &self.numerator * SCALE / &self.denominator
+        }
+    }
+
+    fn random_amount(rng: &mut StdRng) -> BigUint {
+        // anywhere from a few dollars to far past what a u128 holds
+        let digits = rng.gen_range(1..120);
+        let text: String = (0..digits)
+            .map(|i| {
+                let low = if i == 0 { b'1' } else { b'0' };
+                char::from(rng.gen_range(low..=b'9'))
+            })
+            .collect();
+
+        BigUint::parse_bytes(text.as_bytes(), 10).unwrap()
+    }
+
+    fn random_millionths(rng: &mut StdRng) -> u64 {
+        match rng.gen_range(0..3) {
+            0 => rng.gen_range(0..10) * SCALE as u64,
+            1 => rng.gen_range(0..10 * SCALE as u64),
+            _ => rng.gen_range(0..u64::MAX / 4),
+        }
+    }
+
+    const CASES: usize = 2000;
+
+    #[test]
+    fn applying_rounds_down_to_a_whole_amount() {
+        // (multiplier in millionths, amount, expected), worked out by hand
+        let cases = [
+            (1_500_000u64, "7", "10"),
+            (1, "999999", "0"),
+            (1, "1000000", "1"),
+            (2_333_333, "3", "6"),
+            (0, "12345", "0"),
+            (1_000_000, "0", "0"),
+            (12_500_000, "8", "100"),
+            (3_141_592, "1000000007", "3141592021"),
+            (
+                750_000,
+                "1000000000000000000000000000000",
+                "750000000000000000000000000000",
+            ),
+            (
+                999_999,
+                "123456789012345678901234567890",
+                "123456665555556666555555666655",
+            ),
+        ];
+
+        for (millionths, amount, expected) in cases {
+            let amount = BigUint::parse_bytes(amount.as_bytes(), 10).unwrap();
+            let expected = BigUint::parse_bytes(expected.as_bytes(), 10).unwrap();
+
+            assert_eq!(
+                Multiplier::from_millionths(millionths).apply(&amount),
+                expected,
+                "{} millionths of {}",
+                millionths,
+                amount
+            );
+        }
+    }
+
+    #[test]
+    fn sums_are_exact() {
+        let mut rng = StdRng::seed_from_u64(380);
+
+        for _ in 0..CASES / 10 {
+            let mut multiplier = Multiplier::default();
+            let mut reference = Fraction::millionths(0);
+
+            // like a long cycle full of PINK triggers
+            for _ in 0..rng.gen_range(1..50) {
+                let millionths = random_millionths(&mut rng);
+                multiplier += &Multiplier::from_millionths(millionths);
+                reference = reference.add(&Fraction::millionths(millionths));
+            }
+
+            let amount = random_amount(&mut rng);
+            assert_eq!(multiplier.apply(&amount), reference.floor_times(&amount));
+        }
+    }
+
+    #[test]
+    fn products_round_down_to_the_nearest_millionth() {
+        let mut rng = StdRng::seed_from_u64(3800);
+
+        for _ in 0..CASES {
+            let (a, b) = (random_millionths(&mut rng), random_millionths(&mut rng));
+            let product = &Multiplier::from_millionths(a) * &Multiplier::from_millionths(b);
+            let reference = Fraction::millionths(a).mul(&Fraction::millionths(b));
+
+            assert_eq!(product, Multiplier::from_millionths(reference.floor_millionths()));
+        }
+    }
+
+    #[test]
+    fn whole_multipliers_are_exact_past_1e38() {
+        let mut rng = StdRng::seed_from_u64(38000);
+
+        for _ in 0..CASES {
+            let amount = random_amount(&mut rng) * BigUint::from(10u32).pow(40);
+            let factor: u64 = rng.gen_range(0..1_000_000_000);
+
+            assert_eq!(
+                Multiplier::from_integer(factor).apply(&amount),
+                &amount * factor
+            );
+        }
+    }
+
+    #[test]
+    fn doubling_bonus_factors_stay_exact() {
+        // each RED in a chain doubles the bonus factor; an f32 runs out after 127 of them
+        let two = Multiplier::from_integer(2u32);
+        let mut bonus_factor = Multiplier::one();
+
+        for _ in 0..300 {
+            bonus_factor = &bonus_factor * &two;
+        }
+
+        assert_eq!(bonus_factor, Multiplier::from_integer(BigUint::from(2u32).pow(300)));
+        assert_eq!(
+            bonus_factor.apply(&BigUint::from(3u32)),
+            BigUint::from(3u32) * BigUint::from(2u32).pow(300)
+        );
+    }
+
+    #[test]
+    fn fractional_bonus_factors_are_not_lost_on_big_amounts() {
+        let amount = BigUint::from(10u32).pow(50);
+        let multiplier = Multiplier::from_f64(1.5);
+
+        assert_eq!(multiplier.apply(&amount), BigUint::from(15u32) * BigUint::from(10u32).pow(49));
+    }
+
+    #[test]
+    fn from_f64_rounds_to_the_nearest_millionth() {
+        assert_eq!(Multiplier::from_f64(1.), Multiplier::one());
+        assert_eq!(Multiplier::from_f64(0.5), Multiplier::from_millionths(500_000u32));
+        assert_eq!(Multiplier::from_f64(0.0000004), Multiplier::default());
+        assert_eq!(Multiplier::from_f64(0.0000006), Multiplier::from_millionths(1u32));
+        assert_eq!(Multiplier::from_f64(-3.), Multiplier::default());
+        assert_eq!(Multiplier::from_f64(f64::NAN), Multiplier::default());
+        assert_eq!(Multiplier::from_f64(f64::INFINITY), Multiplier::default());
+        assert_eq!(
+            Multiplier::from_f64(2f64.powi(100)),
+            Multiplier::from_integer(BigUint::from(2u32).pow(100))
+        );
+    }
+
+    #[test]
+    fn from_f64_matches_f64_arithmetic_where_it_is_exact() {
+        let mut rng = StdRng::seed_from_u64(380_000);
+
+        for _ in 0..CASES {
+            let millionths: u32 = rng.gen_range(0..u32::MAX);
+            let value = millionths as f64 / SCALE as f64;
+            let amount: u32 = rng.gen_range(0..u32::MAX);
+
+            assert_eq!(Multiplier::from_f64(value), Multiplier::from_millionths(millionths));
+            assert_eq!(
+                Multiplier::from_f64(value).apply(&BigUint::from(amount)),
+                BigUint::from(amount as u64 * millionths as u64 / SCALE as u64)
+            );
+        }
+    }
+
+    #[test]
+    fn saturating_sub() {
+        let three = Multiplier::from_integer(3u32);
+        let one = Multiplier::one();
+
+        assert_eq!(three.saturating_sub(&one), Multiplier::from_integer(2u32));
+        assert_eq!(one.saturating_sub(&three), Multiplier::default());
+        assert!(one.saturating_sub(&one).is_zero());
+    }
+
+    #[test]
+    fn ordering_matches_value() {
+        let mut rng = StdRng::seed_from_u64(3_800_000);
+
+        for _ in 0..CASES {
+            let (a, b) = (random_millionths(&mut rng), random_millionths(&mut rng));
+            assert_eq!(
+                Multiplier::from_millionths(a).cmp(&Multiplier::from_millionths(b)),
+                a.cmp(&b)
+            );
+        }
+    }
+
+    #[test]
+    fn to_f32() {
+        assert_eq!(Multiplier::one().to_f32(), 1.);
+        assert_eq!(Multiplier::from_f64(0.5).to_f32(), 0.5);
+        assert_eq!(Multiplier::from_integer(BigUint::from(10u32).pow(60)).to_f32(), f32::MAX);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Multiplier::default().to_string(), "0");
+        assert_eq!(Multiplier::one().to_string(), "1");
+        assert_eq!(Multiplier::from_f64(2.5).to_string(), "2.5");
+        assert_eq!(Multiplier::from_millionths(1u32).to_string(), "0.000001");
+        assert_eq!(Multiplier::from_millionths(12_340_000u32).to_string(), "12.34");
+        assert_eq!(
+            Multiplier::from_integer(BigUint::from(10u32).pow(40)).to_string(),
+            format!("1{}", "0".repeat(40))
+        );
+    }
+}
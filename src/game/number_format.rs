@@ -0,0 +1,454 @@
+//! Turning amounts of money into text.
+//!
+//! Every notation works from the number's decimal digits, so they stay exact however big
+//! the number gets. Compact notations round down, so a price never reads as affordable
+//! before it is. Numbers below [`COMPACT_BELOW`] are short enough to show in full.
+
+use num_bigint::BigUint;
+
+use super::{multiplier::Multiplier, settings::NumberFormat};
+
+/// Numbers below this are written out in full by every notation.
+pub const COMPACT_BELOW: u32 = 10_000;
+
+/// How many significant digits compact notations keep.
+const SIGNIFICANT_DIGITS: usize = 4;
+
+/// Short scale names up to trillions. After that come two-letter suffixes: aa, ab, ..., zz.
+const NAMED_SUFFIXES: [&str; 5] = ["", "K", "M", "B", "T"];
+
+pub fn format_number(num: &BigUint, number_format: NumberFormat) -> String {
+    format_digits(&num.to_str_radix(10), number_format)
+}
+
+/// Like [`format_number`], for a number already written out as plain decimal digits.
+pub fn format_digits(digits: &str, number_format: NumberFormat) -> String {
+    match number_format {
+        NumberFormat::Scientific => format_scientific(digits),
+        NumberFormat::Engineering => format_engineering(digits),
+        NumberFormat::Suffixes => format_suffixes(digits),
+        NumberFormat::Full => format_full(digits),
+        NumberFormat::Logarithmic => format_logarithmic(digits),
+    }
+}
+
+/// Multipliers get two decimal places at most, rounded down: `1.5`, `2`, `0.25`.
+/// Ones too big for their fraction to matter are written like money.
+pub fn format_multiplier(multiplier: &Multiplier, number_format: NumberFormat) -> String {
+    let integer = multiplier.integer_part();
+
+    if integer >= BigUint::from(COMPACT_BELOW) {
+        return format_number(&integer, number_format);
+    }
+
+    let hundredths = format!("{:02}", multiplier.fraction_millionths() / 10_000);
+    let hundredths = hundredths.trim_end_matches('0');
+
+    if hundredths.is_empty() {
+        integer.to_string()
+    } else {
+        format!("{}.{}", integer, hundredths)
+    }
+}
+
+/// `12345` is `1.234e4`.
+fn format_scientific(digits: &str) -> String {
+    if is_small(digits) {
+        return digits.to_string();
+    }
+
+    let exponent = digits.len() - 1;
+    format!("{}e{}", mantissa(digits, 1), exponent)
+}
+
+/// Like scientific, but the exponent is always a multiple of three: `12345` is `12.34e3`.
+fn format_engineering(digits: &str) -> String {
+    if is_small(digits) {
+        return digits.to_string();
+    }
+
+    let exponent = (digits.len() - 1) / 3 * 3;
+    format!("{}e{}", mantissa(digits, digits.len() - exponent), exponent)
+}
+
+/// `12345` is `12.34K`. Numbers too big for even the last suffix fall back to scientific.
+fn format_suffixes(digits: &str) -> String {
+    if is_small(digits) {
+        return digits.to_string();
+    }
+
+    let group = (digits.len() - 1) / 3;
+
+    match short_scale_suffix(group) {
+        Some(suffix) => format!("{}{}", mantissa(digits, digits.len() - group * 3), suffix),
+        None => format_scientific(digits),
+    }
+}
+
+/// Every digit, grouped in threes: `1,234,567`.
+fn format_full(digits: &str) -> String {
+    let mut result = String::with_capacity(digits.len() + digits.len() / 3);
+
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            result.push(',');
+        }
+        result.push(digit);
+    }
+
+    result
+}
+
+/// The base 10 logarithm, to two decimal places: `12345` is `e4.09`.
+fn format_logarithmic(digits: &str) -> String {
+    if is_small(digits) {
+        return digits.to_string();
+    }
+
+    // the leading digits hold everything the fraction needs; the rest only add to the exponent
+    let leading = &digits[..digits.len().min(15)];
+    let leading_value: f64 = leading.parse().unwrap_or(1.);
+    let fraction = (leading_value.log10() - (leading.len() - 1) as f64).clamp(0., 1.);
+
+    // work in hundredths so rounding down can't print .100
+    let hundredths = ((fraction * 100.) + 1e-9).floor().min(99.) as usize;
+
+    format!("e{}.{:02}", digits.len() - 1, hundredths)
+}
+
+/// The suffix for numbers with `group` groups of three digits after the leading ones.
+/// Group 0 has no suffix, group 1 is `K`, group 5 is `aa`, and group 680 is `zz`.
+pub fn short_scale_suffix(group: usize) -> Option<String> {
+    if let Some(named) = NAMED_SUFFIXES.get(group) {
+        return Some(named.to_string());
+    }
+
+    let lettered = group - NAMED_SUFFIXES.len();
+
+    if lettered >= 26 * 26 {
+        return None;
+    }
+
+    let letter = |index: usize| (b'a' + index as u8) as char;
+
+    Some(format!("{}{}", letter(lettered / 26), letter(lettered % 26)))
+}
+
+fn is_small(digits: &str) -> bool {
+    digits.len() < COMPACT_BELOW.to_string().len()
+}
+
+/// The leading digits of `digits` with a decimal point after the first `integer_digits` of them,
+/// rounded down to [`SIGNIFICANT_DIGITS`] and without trailing zeros: `mantissa("120045", 1)` is `1.2`.
+fn mantissa(digits: &str, integer_digits: usize) -> String {
+    let kept = &digits[..digits.len().min(SIGNIFICANT_DIGITS.max(integer_digits))];
+    let (integer, fraction) = kept.split_at(integer_digits.min(kept.len()));
+    let fraction = fraction.trim_end_matches('0');
+
+    if fraction.is_empty() {
+        integer.to_string()
+    } else {
+        format!("{}.{}", integer, fraction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_formats() -> [NumberFormat; 5] {
+        [
+            NumberFormat::Scientific,
+            NumberFormat::Engineering,
+            NumberFormat::Suffixes,
+            NumberFormat::Full,
+            NumberFormat::Logarithmic,
+        ]
+    }
+
+    fn pow10(exponent: usize) -> String {
+        format!("1{}", "0".repeat(exponent))
+    }
+
+    #[test]
+    fn small_numbers_are_written_in_full() {
+        for number_format in all_formats() {
+            for n in [0u32, 1, 9, 10, 99, 100, 999] {
+                assert_eq!(
+                    format_number(&BigUint::from(n), number_format),
+                    n.to_string(),
+                    "{:?}",
+                    number_format
+                );
+            }
+        }
+
+        for number_format in all_formats() {
+            if number_format == NumberFormat::Full {
+                continue;
+            }
+            assert_eq!(format_number(&BigUint::from(1000u32), number_format), "1000");
+            assert_eq!(format_number(&BigUint::from(9999u32), number_format), "9999");
+        }
+    }
+
+    #[test]
+    fn every_format_handles_zero() {
+        for number_format in all_formats() {
+            assert_eq!(format_number(&BigUint::ZERO, number_format), "0");
+        }
+    }
+
+    #[test]
+    fn scientific() {
+        let cases = [
+            ("10000", "1e4"),
+            ("12345", "1.234e4"),
+            ("12000", "1.2e4"),
+            ("10500", "1.05e4"),
+            ("99999", "9.999e4"),
+            ("100000", "1e5"),
+            ("1000000", "1e6"),
+            ("1200000", "1.2e6"),
+            ("123456789", "1.234e8"),
+        ];
+
+        for (digits, expected) in cases {
+            assert_eq!(format_digits(digits, NumberFormat::Scientific), expected, "{}", digits);
+        }
+    }
+
+    #[test]
+    fn scientific_precision_does_not_depend_on_trailing_zeros() {
+        // these used to come out as 1.000e5 and 1.2e6 depending on how many zeros came after
+        assert_eq!(format_digits("100005", NumberFormat::Scientific), "1e5");
+        assert_eq!(format_digits("1000050", NumberFormat::Scientific), "1e6");
+        assert_eq!(format_digits("1200001", NumberFormat::Scientific), "1.2e6");
+        assert_eq!(format_digits("1230000", NumberFormat::Scientific), "1.23e6");
+    }
+
+    #[test]
+    fn scientific_rounds_down() {
+        assert_eq!(format_digits("19999", NumberFormat::Scientific), "1.999e4");
+        assert_eq!(format_digits("99999999", NumberFormat::Scientific), "9.999e7");
+    }
+
+    #[test]
+    fn scientific_huge_numbers() {
+        assert_eq!(format_digits(&pow10(38), NumberFormat::Scientific), "1e38");
+        assert_eq!(format_digits(&pow10(1000), NumberFormat::Scientific), "1e1000");
+
+        let big = BigUint::from(7u32).pow(1000);
+        let formatted = format_number(&big, NumberFormat::Scientific);
+        assert_eq!(formatted, "1.253e845");
+    }
+
+    #[test]
+    fn engineering() {
+        let cases = [
+            ("10000", "10e3"),
+            ("12345", "12.34e3"),
+            ("123456", "123.4e3"),
+            ("999999", "999.9e3"),
+            ("1000000", "1e6"),
+            ("1234567", "1.234e6"),
+            ("12340000", "12.34e6"),
+            ("120000000", "120e6"),
+            ("1000000000", "1e9"),
+        ];
+
+        for (digits, expected) in cases {
+            assert_eq!(format_digits(digits, NumberFormat::Engineering), expected, "{}", digits);
+        }
+    }
+
+    #[test]
+    fn engineering_exponent_is_a_multiple_of_three() {
+        for exponent in 4..200 {
+            let formatted = format_digits(&pow10(exponent), NumberFormat::Engineering);
+            let (mantissa, exponent_text) = formatted.split_once('e').unwrap();
+            let shown_exponent: usize = exponent_text.parse().unwrap();
+
+            assert_eq!(shown_exponent % 3, 0, "{}", formatted);
+            assert_eq!(shown_exponent + mantissa.len() - 1, exponent, "{}", formatted);
+        }
+    }
+
+    #[test]
+    fn suffixes() {
+        let cases = [
+            ("10000", "10K"),
+            ("12345", "12.34K"),
+            ("123456", "123.4K"),
+            ("999999", "999.9K"),
+            ("1000000", "1M"),
+            ("1500000", "1.5M"),
+            ("1000000000", "1B"),
+            ("1000000000000", "1T"),
+            ("1234000000000", "1.234T"),
+        ];
+
+        for (digits, expected) in cases {
+            assert_eq!(format_digits(digits, NumberFormat::Suffixes), expected, "{}", digits);
+        }
+    }
+
+    #[test]
+    fn suffixes_after_trillions_are_lettered() {
+        assert_eq!(format_digits(&pow10(15), NumberFormat::Suffixes), "1aa");
+        assert_eq!(format_digits(&pow10(16), NumberFormat::Suffixes), "10aa");
+        assert_eq!(format_digits(&pow10(18), NumberFormat::Suffixes), "1ab");
+        assert_eq!(format_digits(&pow10(15 + 3 * 25), NumberFormat::Suffixes), "1az");
+        assert_eq!(format_digits(&pow10(15 + 3 * 26), NumberFormat::Suffixes), "1ba");
+    }
+
+    #[test]
+    fn short_scale_suffix_sequence() {
+        assert_eq!(short_scale_suffix(0).as_deref(), Some(""));
+        assert_eq!(short_scale_suffix(1).as_deref(), Some("K"));
+        assert_eq!(short_scale_suffix(2).as_deref(), Some("M"));
+        assert_eq!(short_scale_suffix(3).as_deref(), Some("B"));
+        assert_eq!(short_scale_suffix(4).as_deref(), Some("T"));
+        assert_eq!(short_scale_suffix(5).as_deref(), Some("aa"));
+        assert_eq!(short_scale_suffix(6).as_deref(), Some("ab"));
+        assert_eq!(short_scale_suffix(30).as_deref(), Some("az"));
+        assert_eq!(short_scale_suffix(31).as_deref(), Some("ba"));
+        assert_eq!(short_scale_suffix(680).as_deref(), Some("zz"));
+        assert_eq!(short_scale_suffix(681), None);
+    }
+
+    #[test]
+    fn short_scale_suffixes_are_unique_and_ordered() {
+        let suffixes: Vec<String> = (5..=680).map(|group| short_scale_suffix(group).unwrap()).collect();
+
+        for pair in suffixes.windows(2) {
+            assert!(pair[0] < pair[1], "{} should come before {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn suffixes_fall_back_to_scientific_past_zz() {
+        let last = pow10(3 * 680 + 2);
+        assert_eq!(format_digits(&last, NumberFormat::Suffixes), "100zz");
+
+        let past_last = pow10(3 * 681);
+        assert_eq!(
+            format_digits(&past_last, NumberFormat::Suffixes),
+            format!("1e{}", 3 * 681)
+        );
+    }
+
+    #[test]
+    fn full() {
+        let cases = [
+            ("0", "0"),
+            ("999", "999"),
+            ("1000", "1,000"),
+            ("9999", "9,999"),
+            ("12345", "12,345"),
+            ("123456", "123,456"),
+            ("1234567", "1,234,567"),
+            ("1000000000", "1,000,000,000"),
+        ];
+
+        for (digits, expected) in cases {
+            assert_eq!(format_digits(digits, NumberFormat::Full), expected, "{}", digits);
+        }
+    }
+
+    #[test]
+    fn full_keeps_every_digit() {
+        for length in 1..60 {
+            let digits: String = (0..length).map(|i| char::from(b'1' + (i % 9) as u8)).collect();
+            let formatted = format_digits(&digits, NumberFormat::Full);
+
+            assert_eq!(formatted.replace(',', ""), digits);
+            assert!(formatted.split(',').skip(1).all(|group| group.len() == 3));
+            assert!((1..=3).contains(&formatted.split(',').next().unwrap().len()));
+        }
+    }
+
+    #[test]
+    fn logarithmic() {
+        let cases = [
+            ("10000", "e4.00"),
+            ("12345", "e4.09"),
+            ("31622", "e4.49"),
+            ("31623", "e4.50"),
+            ("99999", "e4.99"),
+            ("100000", "e5.00"),
+            ("1000000", "e6.00"),
+        ];
+
+        for (digits, expected) in cases {
+            assert_eq!(format_digits(digits, NumberFormat::Logarithmic), expected, "{}", digits);
+        }
+    }
+
+    #[test]
+    fn logarithmic_huge_numbers() {
+        assert_eq!(format_digits(&pow10(1000), NumberFormat::Logarithmic), "e1000.00");
+        assert_eq!(
+            format_digits(&format!("2{}", "0".repeat(500)), NumberFormat::Logarithmic),
+            "e500.30"
+        );
+        assert_eq!(format_digits(&"9".repeat(400), NumberFormat::Logarithmic), "e399.99");
+    }
+
+    #[test]
+    fn compact_formats_never_overstate() {
+        // rounding down means the leading digits shown are never more than the real ones
+        for digits in ["19999", "99999", "123999", "999999999", "4999999999999"] {
+            let scientific = format_digits(digits, NumberFormat::Scientific);
+            let shown: String = scientific
+                .split('e')
+                .next()
+                .unwrap()
+                .chars()
+                .filter(|c| c.is_ascii_digit())
+                .collect();
+
+            assert!(digits.starts_with(&shown), "{} became {}", digits, scientific);
+        }
+    }
+
+    #[test]
+    fn multipliers() {
+        let format = |value: f64| format_multiplier(&Multiplier::from_f64(value), NumberFormat::Scientific);
+
+        assert_eq!(format(1.), "1");
+        assert_eq!(format(2.5), "2.5");
+        assert_eq!(format(0.25), "0.25");
+        assert_eq!(format(3.333), "3.33");
+        assert_eq!(format(3.339), "3.33");
+        assert_eq!(format(10.), "10");
+        assert_eq!(format(0.), "0");
+        assert_eq!(format(100.), "100");
+        assert_eq!(format(9999.99), "9999.99");
+    }
+
+    #[test]
+    fn huge_multipliers_are_written_like_money() {
+        let multiplier = Multiplier::from_integer(BigUint::from(10u32).pow(20));
+
+        assert_eq!(format_multiplier(&multiplier, NumberFormat::Scientific), "1e20");
+        assert_eq!(format_multiplier(&multiplier, NumberFormat::Suffixes), "100ab");
+        assert_eq!(format_multiplier(&Multiplier::from_f64(12345.67), NumberFormat::Full), "12,345");
+    }
+
+    #[test]
+    fn number_format_cycles_through_every_format() {
+        let mut number_format = NumberFormat::default();
+        let mut seen = vec![];
+
+        for _ in 0..all_formats().len() {
+            seen.push(number_format);
+            number_format = number_format.next();
+        }
+
+        assert_eq!(number_format, NumberFormat::default());
+        for expected in all_formats() {
+            assert!(seen.contains(&expected), "{:?} is never reached", expected);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Reading and writing small RON files to the player's config directory.
+//!
+//! Only native builds touch the filesystem. On web, loads always come back empty
+//! and saves are dropped, so everything falls back to its defaults on refresh.
+
+use serde::{de::DeserializeOwned, Serialize};
+
+#[cfg(not(target_family = "wasm"))]
+use bevy::log::warn;
+#[cfg(not(target_family = "wasm"))]
+use std::path::PathBuf;
+
+#[cfg(not(target_family = "wasm"))]
+const APP_DIRECTORY: &str = "every-few-seconds";
+
+/// The directory our files live in, e.g. `~/.config/every-few-seconds` on Linux.
+#[cfg(not(target_family = "wasm"))]
+fn data_dir() -> Option<PathBuf> {
+    let base = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+
+    base.map(|base| base.join(APP_DIRECTORY))
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub fn load<T: DeserializeOwned>(file_name: &str) -> Option<T> {
+    let path = data_dir()?.join(file_name);
+    let contents = std::fs::read_to_string(&path).ok()?;
+
+    match ron::from_str(&contents) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            warn!("Ignoring unreadable {}: {}", path.display(), err);
+            None
+        }
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub fn save<T: Serialize>(file_name: &str, value: &T) {
+    let Some(dir) = data_dir() else {
+        warn!("No config directory available, not saving {}", file_name);
+        return;
+    };
+
+    let contents = match ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default()) {
+        Ok(contents) => contents,
+        Err(err) => {
+            warn!("Failed to serialize {}: {}", file_name, err);
+            return;
+        }
+    };
+
+    if let Err(err) = std::fs::create_dir_all(&dir)
+        .and_then(|_| std::fs::write(dir.join(file_name), contents))
+    {
+        warn!("Failed to write {}: {}", file_name, err);
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub fn delete(file_name: &str) {
+    let Some(dir) = data_dir() else {
+        return;
+    };
+
+    match std::fs::remove_file(dir.join(file_name)) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+            warn!("Failed to delete {}: {}", file_name, err);
+        }
+        _ => {}
+    }
+}
+
+#[cfg(target_family = "wasm")]
+pub fn load<T: DeserializeOwned>(_file_name: &str) -> Option<T> {
+    None
+}
+
+#[cfg(target_family = "wasm")]
+pub fn save<T: Serialize>(_file_name: &str, _value: &T) {}
+
+#[cfg(target_family = "wasm")]
+pub fn delete(_file_name: &str) {}
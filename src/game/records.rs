@@ -0,0 +1,92 @@
+//! The best finished runs, kept across sessions so the title screen can show them off.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::persistence;
+
+const RECORDS_FILE: &str = "records.ron";
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(persistence::load::<Records>(RECORDS_FILE).unwrap_or_default());
+
+    app.add_systems(Update, save_records);
+}
+
+/// How quickly a run was won.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum Rank {
+    S,
+    A,
+    B,
+    C,
+    D,
+}
+
+impl Rank {
+    pub fn from_seconds(seconds: f32) -> Self {
+        let minutes = seconds / 60.;
+
+        if minutes < 15. {
+            Rank::S
+        } else if minutes < 25. {
+            Rank::A
+        } else if minutes < 40. {
+            Rank::B
+        } else if minutes < 60. {
+            Rank::C
+        } else {
+            Rank::D
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Rank::S => "S",
+            Rank::A => "A",
+            Rank::B => "B",
+            Rank::C => "C",
+            Rank::D => "D",
+        }
+    }
+}
+
+#[derive(Resource, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Records {
+    /// The fastest win, in seconds of play.
+    pub best_seconds: Option<f32>,
+    pub wins: u32,
+}
+
+impl Records {
+    /// Counts a win, returning whether it was the fastest yet.
+    pub fn record_win(&mut self, seconds: f32) -> bool {
+        self.wins += 1;
+
+        let is_best = self.best_seconds.is_none_or(|best| seconds < best);
+        if is_best {
+            self.best_seconds = Some(seconds);
+        }
+
+        is_best
+    }
+}
+
+/// `mm:ss`, or `h:mm:ss` for long runs.
+pub fn format_play_time(seconds: f32) -> String {
+    let seconds = seconds as u32;
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
+
+fn save_records(records: Res<Records>) {
+    if records.is_changed() && !records.is_added() {
+        persistence::save(RECORDS_FILE, &*records);
+    }
+}
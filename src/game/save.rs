@@ -0,0 +1,146 @@
+//! Saving a run to one of a few slots, so it can be picked back up later.
+//!
+//! A save only keeps what can't be worked out again: what's in each ring's sockets, what's been
+//! bought and how much money there is. Everything else is rebuilt from that when it's loaded.
+
+use bevy::prelude::*;
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    screen::playing::{Currency, RunStats},
+    ui::{
+        shop::{UpgradeHistory, UpgradeKind},
+        toast::ShowToast,
+    },
+};
+
+use super::{
+    persistence,
+    spawn::level::{Ring, Socket, SocketColor},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<CurrentSaveSlot>();
+
+    app.observe(save_run);
+}
+
+/// Trigger this event to save the current run to the [`CurrentSaveSlot`].
+#[derive(Event)]
+pub struct SaveRun;
+
+/// Which slot the current run saves to.
+#[derive(Resource, Default)]
+pub struct CurrentSaveSlot(pub usize);
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct RunSave {
+    /// Big numbers are kept as decimal strings.
+    pub currency: String,
+    pub total_earned: String,
+    pub play_seconds: f32,
+    pub endless: bool,
+    pub cheated: bool,
+    pub upgrades: Vec<UpgradeKind>,
+    /// The color in each socket, ring by ring.
+    pub rings: Vec<Vec<SocketColor>>,
+    /// Seconds since the Unix epoch.
+    pub last_played: u64,
+}
+
+impl RunSave {
+    pub fn currency(&self) -> BigUint {
+        parse_amount(&self.currency)
+    }
+
+    pub fn total_earned(&self) -> BigUint {
+        parse_amount(&self.total_earned)
+    }
+}
+
+fn parse_amount(amount: &str) -> BigUint {
+    BigUint::parse_bytes(amount.as_bytes(), 10).unwrap_or_default()
+}
+
+/// How many runs can be saved side by side.
+pub const SAVE_SLOTS: usize = 3;
+
+pub fn slot_file(slot: usize) -> String {
+    format!("save_{}.ron", slot + 1)
+}
+
+pub fn load_slot(slot: usize) -> Option<RunSave> {
+    persistence::load(&slot_file(slot))
+}
+
+pub fn delete_slot(slot: usize) {
+    persistence::delete(&slot_file(slot));
+}
+
+/// The slot that was played most recently, if any has been saved to.
+pub fn latest_slot() -> Option<(usize, RunSave)> {
+    (0..SAVE_SLOTS)
+        .filter_map(|slot| load_slot(slot).map(|save| (slot, save)))
+        .max_by_key(|(_, save)| save.last_played)
+}
+
+/// Where a new run can save without overwriting anything, if anywhere.
+pub fn empty_slot() -> Option<usize> {
+    (0..SAVE_SLOTS).find(|slot| load_slot(*slot).is_none())
+}
+
+/// Seconds since the Unix epoch. Web builds don't save, so they don't need the real time.
+#[cfg(not(target_family = "wasm"))]
+pub fn now_unix_seconds() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+#[cfg(target_family = "wasm")]
+pub fn now_unix_seconds() -> u64 {
+    0
+}
+
+fn save_run(
+    _trigger: Trigger<SaveRun>,
+    mut commands: Commands,
+    slot: Res<CurrentSaveSlot>,
+    currency: Res<Currency>,
+    run_stats: Res<RunStats>,
+    upgrade_history: Res<UpgradeHistory>,
+    q_ring: Query<&Ring>,
+    q_socket: Query<&Socket>,
+) {
+    let mut rings: Vec<&Ring> = q_ring.iter().collect();
+    rings.sort_by_key(|ring| ring.index);
+
+    let save = RunSave {
+        currency: currency.amount.to_str_radix(10),
+        total_earned: run_stats.total_earned.to_str_radix(10),
+        play_seconds: run_stats.play_seconds,
+        endless: run_stats.endless,
+        cheated: run_stats.cheated,
+        upgrades: upgrade_history.history.iter().copied().collect(),
+        rings: rings
+            .iter()
+            .map(|ring| {
+                ring.sockets
+                    .iter()
+                    .map(|socket| q_socket.get(*socket).map_or(SocketColor::NONE, |socket| socket.color))
+                    .collect()
+            })
+            .collect(),
+        last_played: now_unix_seconds(),
+    };
+
+    persistence::save(&slot_file(slot.0), &save);
+
+    commands.trigger(ShowToast {
+        title: String::from("Saved"),
+        body: format!("Slot {}", slot.0 + 1),
+        ..default()
+    });
+}
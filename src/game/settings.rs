@@ -0,0 +1,171 @@
+//! Player settings. These are loaded from disk on startup, applied live whenever
+//! they change, and written back out so they survive a restart.
+
+use bevy::{
+    prelude::*,
+    window::{PresentMode, PrimaryWindow, WindowMode},
+};
+use serde::{Deserialize, Serialize};
+
+use super::persistence;
+
+const SETTINGS_FILE: &str = "settings.ron";
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(persistence::load::<Settings>(SETTINGS_FILE).unwrap_or_default());
+
+    app.add_systems(
+        Update,
+        (
+            apply_display_settings.run_if(resource_changed::<Settings>),
+            save_settings,
+        ),
+    );
+}
+
+#[derive(Resource, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub ui_volume: f32,
+    pub fullscreen: bool,
+    pub vsync: bool,
+    pub number_format: NumberFormat,
+    pub scrolling_text: ScrollingTextDensity,
+    /// Skip purely decorative movement, like floating text and camera tweens.
+    pub reduce_motion: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            master_volume: 0.3,
+            music_volume: 1.,
+            sfx_volume: 1.,
+            ui_volume: 1.,
+            fullscreen: false,
+            vsync: true,
+            number_format: NumberFormat::default(),
+            scrolling_text: ScrollingTextDensity::default(),
+            reduce_motion: false,
+        }
+    }
+}
+
+/// How amounts of money are written. See `game::number_format` for what each looks like.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum NumberFormat {
+    /// `1.234e15`
+    #[default]
+    Scientific,
+    /// `1.234e15`, but with the exponent a multiple of three: `12.34e3`.
+    Engineering,
+    /// `1.234T`, then `1.234aa`, `1.234ab` and so on.
+    Suffixes,
+    /// `1,234,000,000,000,000`
+    Full,
+    /// `e15.09`
+    Logarithmic,
+}
+
+impl NumberFormat {
+    pub fn next(&self) -> Self {
+        match self {
+            NumberFormat::Scientific => NumberFormat::Engineering,
+            NumberFormat::Engineering => NumberFormat::Suffixes,
+            NumberFormat::Suffixes => NumberFormat::Full,
+            NumberFormat::Full => NumberFormat::Logarithmic,
+            NumberFormat::Logarithmic => NumberFormat::Scientific,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NumberFormat::Scientific => "Scientific",
+            NumberFormat::Engineering => "Engineering",
+            NumberFormat::Suffixes => "Suffixes",
+            NumberFormat::Full => "Full",
+            NumberFormat::Logarithmic => "Logarithmic",
+        }
+    }
+}
+
+/// How much of the floating text above rings gets spawned.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum ScrollingTextDensity {
+    /// Every socket trigger, multiplier and bonus.
+    #[default]
+    All,
+    /// Only the payout at the end of each cycle.
+    Reduced,
+    Off,
+}
+
+impl ScrollingTextDensity {
+    pub fn next(&self) -> Self {
+        match self {
+            ScrollingTextDensity::All => ScrollingTextDensity::Reduced,
+            ScrollingTextDensity::Reduced => ScrollingTextDensity::Off,
+            ScrollingTextDensity::Off => ScrollingTextDensity::All,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScrollingTextDensity::All => "All",
+            ScrollingTextDensity::Reduced => "Reduced",
+            ScrollingTextDensity::Off => "Off",
+        }
+    }
+
+    pub fn shows_details(&self) -> bool {
+        *self == ScrollingTextDensity::All
+    }
+
+    pub fn shows_totals(&self) -> bool {
+        *self != ScrollingTextDensity::Off
+    }
+}
+
+fn apply_display_settings(
+    settings: Res<Settings>,
+    mut q_windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut window) = q_windows.get_single_mut() else {
+        return;
+    };
+
+    window.mode = if settings.fullscreen {
+        WindowMode::BorderlessFullscreen
+    } else {
+        WindowMode::Windowed
+    };
+
+    window.present_mode = if settings.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+}
+
+/// Dragging a slider changes the settings every frame, so wait for them to settle before writing.
+const SAVE_DELAY_SECONDS: f32 = 0.5;
+
+fn save_settings(
+    settings: Res<Settings>,
+    time: Res<Time<Real>>,
+    mut last_change_seconds: Local<Option<f32>>,
+) {
+    if settings.is_changed() && !settings.is_added() {
+        *last_change_seconds = Some(time.elapsed_seconds());
+    }
+
+    if let Some(changed_at) = *last_change_seconds {
+        if time.elapsed_seconds() - changed_at >= SAVE_DELAY_SECONDS {
+            persistence::save(SETTINGS_FILE, &*settings);
+            *last_change_seconds = None;
+        }
+    }
+}
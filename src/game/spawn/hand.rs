@@ -0,0 +1,127 @@
+//! The hand that sweeps around each ring, pointing at where the cycle is up to.
+
+use bevy::{color::palettes::css::WHITE, prelude::*, sprite::MaterialMesh2dBundle};
+use bevy_mod_picking::picking_core::Pickable;
+
+use crate::game::{
+    lod::RingLod,
+    materials::materials::{HandMaterial, LONG_AGO_SECONDS},
+};
+
+use super::level::{
+    map_socket_highlight_color, GameplayMeshes, Ring, SocketColor, RING_RADIUS, RING_THICKNESS,
+};
+
+/// Half the hand's length, in the ring quad's uv space, so it reaches just short of the ring.
+const HAND_WIDTH: f32 = (RING_RADIUS - RING_THICKNESS) / 2. * 0.9;
+/// Half the hand's thickness. It doubles while flashing.
+const HAND_HEIGHT: f32 = 0.012;
+const HAND_FLASH_SECONDS: f32 = 0.3;
+
+pub(super) fn plugin(app: &mut App) {
+    app.observe(flash_hand);
+
+    app.add_systems(Update, (spawn_ring_hands, sync_hand_material_cycle).chain());
+}
+
+/// Trigger this when the hand sweeps past a socket that goes off, to flash the hand in its color.
+#[derive(Event)]
+pub struct FlashHand {
+    pub ring: Entity,
+    pub color: SocketColor,
+}
+
+#[derive(Component)]
+pub struct RingHand {
+    pub ring: Entity,
+}
+
+fn spawn_ring_hands(
+    mut commands: Commands,
+    q_new_rings: Query<Entity, Added<Ring>>,
+    gameplay_meshes: Res<GameplayMeshes>,
+    mut hand_materials: ResMut<Assets<HandMaterial>>,
+) {
+    for ring_entity in &q_new_rings {
+        commands.entity(ring_entity).with_children(|ring_children| {
+            ring_children.spawn((
+                Name::new("Ring Hand"),
+                MaterialMesh2dBundle {
+                    mesh: gameplay_meshes.quad512.clone(),
+                    material: hand_materials.add(HandMaterial {
+                        data: Vec4::new(HAND_WIDTH, HAND_HEIGHT, 0., 0.),
+                        flash_color: WHITE.into(),
+                        flash: Vec4::new(LONG_AGO_SECONDS, HAND_FLASH_SECONDS, 0., 0.),
+                    }),
+                    // above the ring, below its sockets
+                    transform: Transform::from_xyz(0., 0., 0.5),
+                    ..default()
+                },
+                Pickable::IGNORE,
+                RingHand { ring: ring_entity },
+            ));
+        });
+    }
+}
+
+/// Like the ring's own material, the hand only needs to know when the cycle started and how long
+/// it lasts. The shader sweeps it from there, so it stays in step when the cycle speeds up.
+fn sync_hand_material_cycle(
+    q_hand: Query<(&RingHand, &Handle<HandMaterial>)>,
+    q_ring: Query<Ref<Ring>>,
+    mut hand_materials: ResMut<Assets<HandMaterial>>,
+) {
+    for (hand, hand_material_handle) in &q_hand {
+        let Ok(ring) = q_ring.get(hand.ring) else {
+            continue;
+        };
+
+        if !ring.is_changed() {
+            continue;
+        }
+
+        let Some(hand_material) = hand_materials.get(hand_material_handle) else {
+            continue;
+        };
+
+        if hand_material.data.z == ring.cycle_start_seconds
+            && hand_material.data.w == ring.cycle_duration
+        {
+            continue;
+        }
+
+        if let Some(hand_material) = hand_materials.get_mut(hand_material_handle) {
+            hand_material.data.z = ring.cycle_start_seconds;
+            hand_material.data.w = ring.cycle_duration;
+        }
+    }
+}
+
+fn flash_hand(
+    trigger: Trigger<FlashHand>,
+    q_ring: Query<(&Children, Option<&RingLod>), With<Ring>>,
+    q_hand: Query<&Handle<HandMaterial>, With<RingHand>>,
+    mut hand_materials: ResMut<Assets<HandMaterial>>,
+    time: Res<Time>,
+) {
+    let event = trigger.event();
+
+    let Ok((children, lod)) = q_ring.get(event.ring) else {
+        return;
+    };
+
+    // zoomed out too far to see the hand
+    if lod.is_some_and(|lod| lod.simplified) {
+        return;
+    }
+
+    let Some(hand_material_handle) = children.iter().find_map(|child| q_hand.get(*child).ok())
+    else {
+        return;
+    };
+
+    if let Some(hand_material) = hand_materials.get_mut(hand_material_handle) {
+        hand_material.flash_color = map_socket_highlight_color(event.color);
+        hand_material.flash.x = time.elapsed_seconds();
+    }
+}
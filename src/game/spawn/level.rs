@@ -1,6 +1,6 @@
 //! Spawn the main level by triggering other observers.
 
-use std::{collections::VecDeque, f32::consts::PI};
+use std::f32::consts::PI;
 
 use bevy::{
     audio::{PlaybackMode, Volume},
@@ -18,21 +18,26 @@ use bevy::{
     utils::hashbrown::HashMap,
 };
 use bevy_mod_picking::{
-    events::{Click, Pointer},
+    events::{Click, Out, Over, Pointer},
     picking_core::Pickable,
     pointer::PointerButton,
     prelude::On,
 };
 use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     game::{
         assets::{HandleMap, SfxKey},
-        audio::soundtrack::PlaySfx,
+        audio::{mixer::AudioBus, soundtrack::PlaySfx},
         materials::materials::{RingMaterial, SocketMaterial},
+        multiplier::Multiplier,
+    },
+    screen::{playing::{CycleBonus, RunStart}, InRun},
+    ui::{
+        socket_tooltip::{HideSocketTooltip, ShowSocketTooltip},
+        widgets::Hotbar,
     },
-    screen::playing::{Currency, CycleBonus},
-    ui::widgets::Hotbar,
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -41,6 +46,7 @@ pub(super) fn plugin(app: &mut App) {
     app.init_resource::<GameplayMeshes>();
     app.init_resource::<RingIndex>();
     app.add_systems(Startup, prepare_meshes);
+    app.add_systems(Update, sync_ring_material_cycle);
 }
 
 #[derive(Resource, Default)]
@@ -69,11 +75,18 @@ pub struct Ring {
     pub pending_amount: BigUint,
     pub cycle_start_seconds: f32,
     pub cycle_duration: f32,
+    /// How far through the cycle the ring was last frame, to tell which sockets the hand just passed.
+    pub last_progress_pct: f32,
     pub cycle_score: BigUint,
+    /// What the ring paid out at the end of its last cycle.
+    pub previous_cycle_score: BigUint,
+    /// The biggest bonus the ring has earned in one cycle. Only new records make it into the event log.
+    pub best_bonus_score: BigUint,
     pub cycle_count: BigUint,
-    pub cycle_multiplier: f32,
+    pub cycle_multiplier: Multiplier,
     pub sockets: Vec<Entity>,
-    pub cycle_display_panels: VecDeque<Entity>,
+    /// The entity drawing this cycle's triggers above the ring, once it's been spawned.
+    pub cycle_display: Option<Entity>,
     pub index: usize,
 }
 
@@ -85,7 +98,7 @@ pub struct CycleTrigger {
     pub soft: bool,
 }
 
-#[derive(Default, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[derive(Default, PartialEq, Eq, Hash, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum SocketColor {
     #[default]
     NONE,
@@ -112,6 +125,10 @@ impl SocketColor {
 pub const RING_QUAD_DIMENSIONS: Vec2 = Vec2::splat(512.);
 pub const RING_RADIUS: f32 = 1. - 0.005;
 pub const RING_THICKNESS: f32 = 0.05;
+/// The gap between neighbouring rings in the spiral.
+pub const RING_SPACING: f32 = 100.;
+/// How long a new ring's hand takes to go round once, in seconds.
+pub const RING_CYCLE_DURATION: f32 = 4.;
 const DEFAULT_SOCKET_RADIUS: f32 = 32.;
 
 #[derive(Component)]
@@ -122,6 +139,9 @@ pub struct Socket {
     pub index: usize,
     pub last_triggered_time_seconds: f32,
     pub trigger_duration_seconds: f32,
+    /// What this socket has added to its ring's score so far this cycle, before multipliers.
+    pub earned_this_cycle: BigUint,
+    pub earned_last_cycle: BigUint,
 }
 
 #[derive(Resource, Default)]
@@ -136,41 +156,51 @@ fn spawn_level(
     mut ring_materials: ResMut<Assets<RingMaterial>>,
     mut socket_materials: ResMut<Assets<SocketMaterial>>,
     gameplay_meshes: Res<GameplayMeshes>,
-    mut currency: ResMut<Currency>,
+    run_start: Res<RunStart>,
     time: Res<Time>,
+    mut q_camera: Query<&mut Transform, With<Camera>>,
 ) {
-    if cfg!(feature = "dev") {
-        //let large_number_str = "500000000000000000000000000000";
-        let large_number_str = "0";
-        let large_number = BigUint::parse_bytes(large_number_str.as_bytes(), 10)
-            .expect("Failed to parse big number");
-        currency.amount = large_number;
+    let layouts = match &*run_start {
+        RunStart::Load(save) if !save.rings.is_empty() => save.rings.clone(),
+        _ => vec![NEW_RING_SOCKETS.to_vec()],
+    };
+
+    // zoomed out the way buying the second ring does
+    if layouts.len() > 1 {
+        for mut camera_transform in &mut q_camera {
+            camera_transform.scale *= 2.;
+        }
     }
 
-    spawn_ring(
-        &mut commands,
-        ring_index,
-        gameplay_meshes.quad512.clone(),
-        gameplay_meshes.quad64.clone(),
-        ring_materials.add(RingMaterial {
-            data: Vec4::new(RING_RADIUS, RING_THICKNESS, 0., 0.),
-        }),
-        socket_materials,
-        2,
-        time,
-        0,
-    );
+    for (index, socket_colors) in layouts.into_iter().enumerate() {
+        spawn_ring(
+            &mut commands,
+            &mut ring_index,
+            gameplay_meshes.quad512.clone(),
+            gameplay_meshes.quad64.clone(),
+            ring_materials.add(RingMaterial {
+                data: Vec4::new(RING_RADIUS, RING_THICKNESS, 0., 0.),
+            }),
+            &mut socket_materials,
+            socket_colors,
+            &time,
+            index,
+        );
+    }
 }
 
+/// What every new ring comes with: one BLUE orb to get it going and an empty socket.
+pub const NEW_RING_SOCKETS: [SocketColor; 2] = [SocketColor::NONE, SocketColor::BLUE];
+
 pub fn spawn_ring(
     commands: &mut Commands,
-    mut ring_index: ResMut<RingIndex>,
+    ring_index: &mut RingIndex,
     ring_mesh: Mesh2dHandle,
     socket_mesh: Mesh2dHandle,
     ring_material: Handle<RingMaterial>,
-    mut socket_materials: ResMut<Assets<SocketMaterial>>, // every socket needs a UNIQUE material instance
-    num_sockets: usize,
-    time: Res<Time>,
+    socket_materials: &mut Assets<SocketMaterial>, // every socket needs a UNIQUE material instance
+    socket_colors: Vec<SocketColor>,
+    time: &Time,
     index: usize,
 ) {
     let ring_entity = commands
@@ -183,6 +213,7 @@ pub fn spawn_ring(
                 should_block_lower: false,
                 is_hoverable: false,
             },
+            StateScoped(InRun),
         ))
         .id();
 
@@ -192,10 +223,10 @@ pub fn spawn_ring(
         //transform: Transform::from_xyz(index as f32 * 512., 0., 0.),
         transform: Transform::from_translation(get_ring_position_and_update_index(
             index,
-            512.,
-            100.,
+            RING_QUAD_DIMENSIONS.x,
+            RING_SPACING,
             ring_entity,
-            &mut ring_index,
+            ring_index,
         )),
         ..default()
     });
@@ -205,14 +236,9 @@ pub fn spawn_ring(
     commands
         .entity(ring_entity)
         .with_children(|ring_entity_children| {
-            for i in 0..num_sockets {
-                let socket_color = if i == 1 && num_sockets == 2 {
-                    // the first ring spawns pre-socketed with 1 blue
-                    SocketColor::BLUE
-                } else {
-                    SocketColor::NONE
-                };
+            let num_sockets = socket_colors.len();
 
+            for (i, socket_color) in socket_colors.into_iter().enumerate() {
                 let socket_entity = spawn_socket(
                     ring_entity_children,
                     socket_color.clone(),
@@ -229,7 +255,6 @@ pub fn spawn_ring(
                             0.,
                             (socket_color as u8).saturating_sub(1) as f32,
                         ),
-                        data2: Vec4::ZERO,
                     }),
                     socket_position(i, num_sockets).extend(1.),
                 );
@@ -240,14 +265,38 @@ pub fn spawn_ring(
 
     commands.entity(ring_entity).insert(Ring {
         sockets: starting_sockets,
-        cycle_duration: 4.,
+        cycle_duration: RING_CYCLE_DURATION,
         cycle_start_seconds: time.elapsed_seconds(),
-        cycle_multiplier: 1.,
+        cycle_multiplier: Multiplier::one(),
         index,
         ..default()
     });
 }
 
+/// The ring shader works out how far through the cycle it is from the global time,
+/// so its material only needs to change when a new cycle starts.
+fn sync_ring_material_cycle(
+    q_ring: Query<(&Ring, &Handle<RingMaterial>), Changed<Ring>>,
+    mut ring_materials: ResMut<Assets<RingMaterial>>,
+) {
+    for (ring, ring_material_handle) in &q_ring {
+        let Some(ring_material) = ring_materials.get(ring_material_handle) else {
+            continue;
+        };
+
+        if ring_material.data.z == ring.cycle_start_seconds
+            && ring_material.data.w == ring.cycle_duration
+        {
+            continue;
+        }
+
+        if let Some(ring_material) = ring_materials.get_mut(ring_material_handle) {
+            ring_material.data.z = ring.cycle_start_seconds;
+            ring_material.data.w = ring.cycle_duration;
+        }
+    }
+}
+
 pub fn get_grid_coordinates(index: usize) -> IVec2 {
     let mut x = 0;
     let mut y = 0;
@@ -295,6 +344,18 @@ pub fn get_grid_coordinates(index: usize) -> IVec2 {
     IVec2::new(x, y)
 }
 
+/// Where the ring at `coords` in the spiral sits in the world.
+pub fn get_ring_world_position(coords: IVec2) -> Vec3 {
+    get_real_position(coords, RING_QUAD_DIMENSIONS.x, RING_SPACING)
+}
+
+/// The spiral cell, if any, that a point in the world falls in.
+pub fn get_ring_grid_coordinates(world_position: Vec2) -> IVec2 {
+    (world_position / (RING_QUAD_DIMENSIONS.x + RING_SPACING))
+        .round()
+        .as_ivec2()
+}
+
 fn get_real_position(coords: IVec2, cell_size: f32, spacing: f32) -> Vec3 {
     let position_x = coords.x as f32 * (cell_size + spacing);
     let position_y = coords.y as f32 * (cell_size + spacing);
@@ -341,6 +402,8 @@ pub fn spawn_socket(
                 radius: DEFAULT_SOCKET_RADIUS,
                 last_triggered_time_seconds: -100.,
                 trigger_duration_seconds: map_socket_color_trigger_duration(color),
+                earned_this_cycle: BigUint::ZERO,
+                earned_last_cycle: BigUint::ZERO,
             },
             MaterialMesh2dBundle {
                 mesh,
@@ -349,6 +412,12 @@ pub fn spawn_socket(
                 ..default()
             },
         ))
+        .insert(On::<Pointer<Over>>::commands_mut(|ev, commands| {
+            commands.trigger(ShowSocketTooltip { socket: ev.target });
+        }))
+        .insert(On::<Pointer<Out>>::commands_mut(|ev, commands| {
+            commands.trigger(HideSocketTooltip { socket: ev.target });
+        }))
         .insert(On::<Pointer<Click>>::commands_mut(|ev, commands| {
             if ev.event.button == PointerButton::Primary {
                 commands.trigger(UpdateSocketColor {
@@ -413,6 +482,18 @@ pub fn map_socket_color_hotkey(socket_color: SocketColor) -> u32 {
     }
 }
 
+/// Which tone of the soundtrack's current chord a socket of this color plays when it triggers.
+pub fn map_socket_color_chord_tone(socket_color: SocketColor) -> usize {
+    match socket_color {
+        SocketColor::NONE => 0,
+        SocketColor::BLUE => 0,
+        SocketColor::RED => 1,
+        SocketColor::GREEN => 2,
+        SocketColor::ORANGE => 3,
+        SocketColor::PINK => 4,
+    }
+}
+
 #[derive(Event)]
 struct UpdateSocketColor {
     socket: Entity,
@@ -477,12 +558,18 @@ fn on_set_socket_color(
     if socket.color == SocketColor::NONE {
         commands.trigger(PlaySfx {
             key: SfxKey::Affirm,
+            bus: AudioBus::Ui,
             volume: 2.,
+            speed: 1.,
+            position: None,
         });
     } else {
         commands.trigger(PlaySfx {
             key: SfxKey::Neg,
+            bus: AudioBus::Ui,
             volume: 2.,
+            speed: 1.,
+            position: None,
         });
     }
 
@@ -4,8 +4,9 @@
 
 use bevy::prelude::*;
 
+pub mod hand;
 pub mod level;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(level::plugin);
+    app.add_plugins((level::plugin, hand::plugin));
 }
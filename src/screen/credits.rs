@@ -1,20 +1,29 @@
 //! A credits screen that can be accessed from the title screen.
+//!
+//! What it lists comes from `assets/credits.ron`, and rolls up on its own like film credits.
 
-use bevy::prelude::*;
+use bevy::{prelude::*, ui::Val::*};
 
 use super::Screen;
 use crate::{
-    game::{assets::{FontKey, HandleMap, SoundtrackKey}, audio::soundtrack::PlaySoundtrack},
+    game::{
+        assets::{CreditsKey, FontKey, HandleMap, SoundtrackKey},
+        audio::soundtrack::PlaySoundtrack,
+        credits::Credits,
+    },
     ui::prelude::*,
 };
 
+/// How fast the credits roll, in pixels per second.
+const CREDITS_SCROLL_SPEED: f32 = 40.;
+
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Credits), enter_credits);
     app.add_systems(OnExit(Screen::Credits), exit_credits);
 
     app.add_systems(
         Update,
-        handle_credits_action.run_if(in_state(Screen::Credits)),
+        (handle_credits_action, scroll_credits).run_if(in_state(Screen::Credits)),
     );
     app.register_type::<CreditsAction>();
 }
@@ -25,23 +34,114 @@ enum CreditsAction {
     Back,
 }
 
-fn enter_credits(mut commands: Commands,    font_handles: ResMut<HandleMap<FontKey>>,) {
+/// The rolling list, inside a viewport that clips it.
+#[derive(Component, Default)]
+struct CreditsScroll {
+    /// How far it has rolled up from just below the viewport.
+    offset: f32,
+}
+
+fn enter_credits(
+    mut commands: Commands,
+    credits: Res<Assets<Credits>>,
+    credits_handles: Res<HandleMap<CreditsKey>>,
+    font_handles: ResMut<HandleMap<FontKey>>,
+) {
+    let credits = credits.get(&credits_handles[&CreditsKey::Credits]);
+
     commands
         .ui_root()
         .insert(StateScoped(Screen::Credits))
         .with_children(|children| {
-            children.header("Made by");
-            children.label("Alice - Foo");
-            children.label("Bob - Bar");
+            children
+                .spawn((
+                    Name::new("Credits Viewport"),
+                    NodeBundle {
+                        style: Style {
+                            width: Px(500.),
+                            height: Percent(75.),
+                            overflow: Overflow::clip(),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                ))
+                .with_children(|viewport| {
+                    viewport
+                        .spawn((
+                            Name::new("Credits Scroll"),
+                            NodeBundle {
+                                style: Style {
+                                    position_type: PositionType::Absolute,
+                                    // starts out of sight, see `scroll_credits`
+                                    top: Percent(100.),
+                                    flex_direction: FlexDirection::Column,
+                                    align_items: AlignItems::Center,
+                                    row_gap: Px(10.),
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                            CreditsScroll::default(),
+                        ))
+                        .with_children(|list| {
+                            let Some(credits) = credits else {
+                                list.label("Credits are missing!");
+                                return;
+                            };
+
+                            for section in &credits.sections {
+                                list.header(&section.title);
+
+                                for entry in &section.entries {
+                                    list.label(format!(
+                                        "{} - {}",
+                                        entry.role,
+                                        entry.names.join(", ")
+                                    ));
+
+                                    if let Some(license) = &entry.license {
+                                        list.label(format!("({})", license));
+                                    }
+                                }
+                            }
+                        });
+                });
 
-            children.header("Assets");
-            children.label("Bevy logo - All rights reserved by the Bevy Foundation. Permission granted for splash screen use when unmodified.");
             children.button("Back", font_handles[&FontKey::Default].clone()).insert(CreditsAction::Back);
         });
 
     commands.trigger(PlaySoundtrack::Key(SoundtrackKey::Credits));
 }
 
+/// Rolls the credits up through the viewport, starting over once they've gone past the top.
+fn scroll_credits(
+    time: Res<Time>,
+    mut q_scroll: Query<(&mut CreditsScroll, &mut Style, &Node, &Parent)>,
+    q_viewport: Query<&Node>,
+) {
+    for (mut scroll, mut style, node, parent) in &mut q_scroll {
+        let Ok(viewport) = q_viewport.get(parent.get()) else {
+            continue;
+        };
+
+        let viewport_height = viewport.size().y;
+        let content_height = node.size().y;
+
+        // not laid out yet
+        if viewport_height == 0. || content_height == 0. {
+            continue;
+        }
+
+        scroll.offset += CREDITS_SCROLL_SPEED * time.delta_seconds();
+        if scroll.offset > viewport_height + content_height {
+            scroll.offset = 0.;
+        }
+
+        style.top = Px(viewport_height - scroll.offset);
+    }
+}
+
 fn exit_credits(mut commands: Commands) {
     commands.trigger(PlaySoundtrack::Disable);
 }
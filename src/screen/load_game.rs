@@ -0,0 +1,312 @@
+//! A screen listing the save slots, to pick a run back up or clear one out.
+
+use bevy::{
+    color::palettes::{
+        css::{ORANGE, WHITE},
+        tailwind::{GRAY_400, GRAY_800, GRAY_900},
+    },
+    prelude::*,
+    ui::Val::*,
+};
+
+use super::{playing::RunStart, Screen};
+use crate::{
+    game::{
+        assets::{FontKey, HandleMap},
+        number_format::format_number,
+        records::format_play_time,
+        save::{delete_slot, load_slot, now_unix_seconds, CurrentSaveSlot, RunSave, SAVE_SLOTS},
+        settings::Settings,
+    },
+    ui::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::LoadGame), enter_load_game);
+
+    app.observe(list_save_slots);
+
+    app.add_systems(
+        Update,
+        handle_load_game_action.run_if(in_state(Screen::LoadGame)),
+    );
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum LoadGameAction {
+    Load(usize),
+    Delete(usize),
+    ConfirmDelete(usize),
+    CancelDelete,
+    Back,
+}
+
+/// Trigger this event to (re)build the list of save slots.
+#[derive(Event)]
+struct ListSaveSlots;
+
+/// Rebuilt from scratch whenever a slot is deleted.
+#[derive(Component)]
+struct LoadGameRoot;
+
+#[derive(Component)]
+struct ConfirmDeleteDialog;
+
+fn enter_load_game(mut commands: Commands) {
+    commands.trigger(ListSaveSlots);
+}
+
+fn list_save_slots(
+    _trigger: Trigger<ListSaveSlots>,
+    mut commands: Commands,
+    settings: Res<Settings>,
+    font_handles: Res<HandleMap<FontKey>>,
+) {
+    let font = font_handles[&FontKey::Default].clone();
+    let style = |font_size: f32, color: Srgba| TextStyle {
+        font: font.clone(),
+        font_size,
+        color: color.into(),
+    };
+    let now = now_unix_seconds();
+
+    commands
+        .ui_root()
+        .insert((LoadGameRoot, StateScoped(Screen::LoadGame)))
+        .with_children(|children| {
+            children.header("Load game");
+
+            for slot in 0..SAVE_SLOTS {
+                let save = load_slot(slot);
+
+                children
+                    .spawn((
+                        Name::new("Save Slot"),
+                        NodeBundle {
+                            style: Style {
+                                width: Px(520.),
+                                justify_content: JustifyContent::SpaceBetween,
+                                align_items: AlignItems::Center,
+                                padding: UiRect::all(Px(12.)),
+                                border: UiRect::all(Px(2.)),
+                                ..default()
+                            },
+                            background_color: GRAY_800.into(),
+                            border_color: GRAY_900.into(),
+                            ..default()
+                        },
+                    ))
+                    .with_children(|row| {
+                        row.spawn(NodeBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Column,
+                                row_gap: Px(4.),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|details| {
+                            details.spawn(TextBundle::from_section(
+                                format!("Slot {}", slot + 1),
+                                style(20., WHITE),
+                            ));
+
+                            let Some(save) = &save else {
+                                details.spawn(TextBundle::from_section("Empty", style(14., GRAY_400)));
+                                return;
+                            };
+
+                            for line in slot_details(save, &settings, now) {
+                                details.spawn(TextBundle::from_section(line, style(14., GRAY_400)));
+                            }
+                        });
+
+                        if save.is_some() {
+                            row.spawn(NodeBundle {
+                                style: Style {
+                                    column_gap: Px(8.),
+                                    ..default()
+                                },
+                                ..default()
+                            })
+                            .with_children(|buttons| {
+                                buttons
+                                    .button("Load", font.clone())
+                                    .insert(LoadGameAction::Load(slot));
+                                buttons
+                                    .button("Delete", font.clone())
+                                    .insert(LoadGameAction::Delete(slot));
+                            });
+                        }
+                    });
+            }
+
+            children
+                .button("Back", font.clone())
+                .insert(LoadGameAction::Back);
+        });
+}
+
+fn slot_details(save: &RunSave, settings: &Settings, now: u64) -> [String; 4] {
+    [
+        format!(
+            "${}",
+            format_number(&save.currency(), settings.number_format)
+        ),
+        format!(
+            "{} ring{}",
+            save.rings.len(),
+            if save.rings.len() == 1 { "" } else { "s" }
+        ),
+        format!("Played for {}", format_play_time(save.play_seconds)),
+        format!("Last played {}", format_last_played(save.last_played, now)),
+    ]
+}
+
+pub(super) fn format_last_played(last_played: u64, now: u64) -> String {
+    if last_played == 0 || now < last_played {
+        return String::from("a while ago");
+    }
+
+    let ago = |amount: u64, unit: &str| {
+        format!("{} {}{} ago", amount, unit, if amount == 1 { "" } else { "s" })
+    };
+
+    match now - last_played {
+        seconds if seconds < 60 => String::from("just now"),
+        seconds if seconds < 60 * 60 => ago(seconds / 60, "minute"),
+        seconds if seconds < 60 * 60 * 24 => ago(seconds / (60 * 60), "hour"),
+        seconds => ago(seconds / (60 * 60 * 24), "day"),
+    }
+}
+
+fn spawn_confirm_delete(commands: &mut Commands, slot: usize, font: Handle<Font>) {
+    commands
+        .spawn((
+            Name::new("Confirm Delete"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    height: Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                background_color: Color::srgba(0., 0., 0., 0.6).into(),
+                z_index: ZIndex::Global(100),
+                ..default()
+            },
+            ConfirmDeleteDialog,
+            StateScoped(Screen::LoadGame),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Name::new("Confirm Delete Panel"),
+                NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Px(8.),
+                        padding: UiRect::all(Px(16.)),
+                        border: UiRect::all(Px(2.)),
+                        ..default()
+                    },
+                    background_color: GRAY_800.into(),
+                    border_color: GRAY_900.into(),
+                    ..default()
+                },
+            ))
+            .with_children(|panel| {
+                panel.spawn(TextBundle::from_section(
+                    format!("Delete slot {}?", slot + 1),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 30.,
+                        color: WHITE.into(),
+                    },
+                ));
+                panel.spawn(TextBundle::from_section(
+                    "This can't be undone.",
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 16.,
+                        color: ORANGE.into(),
+                    },
+                ));
+
+                panel
+                    .spawn(NodeBundle {
+                        style: Style {
+                            column_gap: Px(16.),
+                            margin: UiRect::top(Px(8.)),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|buttons| {
+                        buttons
+                            .button("Delete", font.clone())
+                            .insert(LoadGameAction::ConfirmDelete(slot));
+                        buttons
+                            .button("Cancel", font.clone())
+                            .insert(LoadGameAction::CancelDelete);
+                    });
+            });
+        });
+}
+
+fn handle_load_game_action(
+    mut commands: Commands,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut run_start: ResMut<RunStart>,
+    mut current_slot: ResMut<CurrentSaveSlot>,
+    mut button_query: InteractionQuery<&LoadGameAction>,
+    q_root: Query<Entity, With<LoadGameRoot>>,
+    q_dialog: Query<Entity, With<ConfirmDeleteDialog>>,
+    font_handles: Res<HandleMap<FontKey>>,
+) {
+    for (interaction, action) in &mut button_query {
+        if !matches!(interaction, Interaction::Pressed) {
+            continue;
+        }
+
+        // the list is still under the dialog, and can be pressed through it
+        let is_dialog_action = matches!(
+            action,
+            LoadGameAction::ConfirmDelete(_) | LoadGameAction::CancelDelete
+        );
+        if !is_dialog_action && !q_dialog.is_empty() {
+            continue;
+        }
+
+        match *action {
+            LoadGameAction::Load(slot) => {
+                // the file could have gone since the list was built
+                if let Some(save) = load_slot(slot) {
+                    *run_start = RunStart::Load(save);
+                    current_slot.0 = slot;
+                    next_screen.set(Screen::Playing);
+                }
+            }
+            LoadGameAction::Delete(slot) => {
+                spawn_confirm_delete(&mut commands, slot, font_handles[&FontKey::Default].clone());
+            }
+            LoadGameAction::ConfirmDelete(slot) => {
+                delete_slot(slot);
+
+                for entity in q_root.iter().chain(&q_dialog) {
+                    commands.entity(entity).despawn_recursive();
+                }
+                commands.trigger(ListSaveSlots);
+            }
+            LoadGameAction::CancelDelete => {
+                for dialog in &q_dialog {
+                    commands.entity(dialog).despawn_recursive();
+                }
+            }
+            LoadGameAction::Back => next_screen.set(Screen::Title),
+        }
+    }
+}
@@ -1,42 +1,246 @@
 //! A loading screen during which game assets are loaded.
 //! This reduces stuttering, especially for audio on WASM.
 
-use bevy::prelude::*;
+use bevy::{
+    color::palettes::tailwind::{GRAY_700, GRAY_900, GREEN_400, RED_400},
+    ecs::system::SystemParam,
+    prelude::*,
+    ui::Val::*,
+};
 
 use super::Screen;
 use crate::{
-    game::assets::{HandleMap, ImageKey, SfxKey, ShaderKey, SoundtrackKey},
-    ui::prelude::*,
+    game::assets::{
+        CreditsKey, FontKey, HandleMap, ImageKey, SfxKey, ShaderKey, SoundtrackKey,
+    },
+    ui::{palette::LABEL_TEXT, prelude::*},
 };
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Loading), enter_loading);
     app.add_systems(
         Update,
-        continue_to_title.run_if(in_state(Screen::Loading).and_then(all_assets_loaded)),
+        (
+            update_loading_progress,
+            handle_loading_action,
+            continue_to_title.run_if(all_assets_loaded),
+        )
+            .run_if(in_state(Screen::Loading)),
     );
 }
 
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum LoadingAction {
+    Retry,
+}
+
+#[derive(Component)]
+struct LoadingBarFill;
+
+#[derive(Component)]
+struct LoadingCountText;
+
+/// Hidden unless something failed to load.
+#[derive(Component)]
+struct LoadingFailure;
+
+#[derive(Component)]
+struct LoadingFailureText;
+
+/// Every [`HandleMap`] that has to be loaded before the game can start.
+#[derive(SystemParam)]
+struct AllAssetHandles<'w> {
+    asset_server: Res<'w, AssetServer>,
+    image_handles: Res<'w, HandleMap<ImageKey>>,
+    sfx_handles: Res<'w, HandleMap<SfxKey>>,
+    soundtrack_handles: Res<'w, HandleMap<SoundtrackKey>>,
+    shader_handles: Res<'w, HandleMap<ShaderKey>>,
+    font_handles: Res<'w, HandleMap<FontKey>>,
+    credits_handles: Res<'w, HandleMap<CreditsKey>>,
+}
+
+impl AllAssetHandles<'_> {
+    fn all_loaded(&self) -> bool {
+        let asset_server = &self.asset_server;
+
+        self.image_handles.all_loaded(asset_server)
+            && self.sfx_handles.all_loaded(asset_server)
+            && self.soundtrack_handles.all_loaded(asset_server)
+            && self.shader_handles.all_loaded(asset_server)
+            && self.font_handles.all_loaded(asset_server)
+            && self.credits_handles.all_loaded(asset_server)
+    }
+
+    /// How many handles are loaded, out of how many there are.
+    fn progress(&self) -> (usize, usize) {
+        let asset_server = &self.asset_server;
+
+        let loaded = self.image_handles.loaded_count(asset_server)
+            + self.sfx_handles.loaded_count(asset_server)
+            + self.soundtrack_handles.loaded_count(asset_server)
+            + self.shader_handles.loaded_count(asset_server)
+            + self.font_handles.loaded_count(asset_server)
+            + self.credits_handles.loaded_count(asset_server);
+        let total = self.image_handles.len()
+            + self.sfx_handles.len()
+            + self.soundtrack_handles.len()
+            + self.shader_handles.len()
+            + self.font_handles.len()
+            + self.credits_handles.len();
+
+        (loaded, total)
+    }
+
+    fn failed_paths(&self) -> Vec<String> {
+        let asset_server = &self.asset_server;
+
+        let mut failed = self.image_handles.failed_paths(asset_server);
+        failed.extend(self.sfx_handles.failed_paths(asset_server));
+        failed.extend(self.soundtrack_handles.failed_paths(asset_server));
+        failed.extend(self.shader_handles.failed_paths(asset_server));
+        failed.extend(self.font_handles.failed_paths(asset_server));
+        failed.extend(self.credits_handles.failed_paths(asset_server));
+        failed
+    }
+}
+
 fn enter_loading(mut commands: Commands) {
+    // our own font may be what's still loading, so this sticks to Bevy's built-in one
+    let text_style = |color: Color| TextStyle {
+        font_size: 20.,
+        color,
+        ..default()
+    };
+
     commands
         .ui_root()
         .insert(StateScoped(Screen::Loading))
         .with_children(|children| {
             children.label("Loading...");
+
+            children
+                .spawn((
+                    Name::new("Loading Bar"),
+                    NodeBundle {
+                        style: Style {
+                            width: Px(400.),
+                            height: Px(24.),
+                            border: UiRect::all(Px(2.)),
+                            ..default()
+                        },
+                        background_color: GRAY_700.into(),
+                        border_color: GRAY_900.into(),
+                        ..default()
+                    },
+                ))
+                .with_children(|bar| {
+                    bar.spawn((
+                        Name::new("Loading Bar Fill"),
+                        NodeBundle {
+                            style: Style {
+                                width: Percent(0.),
+                                height: Percent(100.),
+                                ..default()
+                            },
+                            background_color: GREEN_400.into(),
+                            ..default()
+                        },
+                        LoadingBarFill,
+                    ));
+                });
+
+            children.spawn((
+                Name::new("Loading Count"),
+                TextBundle::from_section("", text_style(LABEL_TEXT)),
+                LoadingCountText,
+            ));
+
+            children
+                .spawn((
+                    Name::new("Loading Failure"),
+                    NodeBundle {
+                        style: Style {
+                            display: Display::None,
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            row_gap: Px(10.),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    LoadingFailure,
+                ))
+                .with_children(|failure| {
+                    failure.spawn((
+                        TextBundle::from_section("", text_style(RED_400.into()))
+                            .with_text_justify(JustifyText::Center),
+                        LoadingFailureText,
+                    ));
+                    failure
+                        .button("Retry", Handle::default())
+                        .insert(LoadingAction::Retry);
+                });
         });
 }
 
-fn all_assets_loaded(
-    asset_server: Res<AssetServer>,
-    image_handles: Res<HandleMap<ImageKey>>,
-    sfx_handles: Res<HandleMap<SfxKey>>,
-    soundtrack_handles: Res<HandleMap<SoundtrackKey>>,
-    shader_handles: Res<HandleMap<ShaderKey>>,
-) -> bool {
-    image_handles.all_loaded(&asset_server)
-        && sfx_handles.all_loaded(&asset_server)
-        && soundtrack_handles.all_loaded(&asset_server)
-        && shader_handles.all_loaded(&asset_server)
+fn update_loading_progress(
+    handles: AllAssetHandles,
+    mut q_fill: Query<&mut Style, With<LoadingBarFill>>,
+    mut q_count_text: Query<&mut Text, (With<LoadingCountText>, Without<LoadingFailureText>)>,
+    mut q_failure: Query<&mut Style, (With<LoadingFailure>, Without<LoadingBarFill>)>,
+    mut q_failure_text: Query<&mut Text, With<LoadingFailureText>>,
+) {
+    let (loaded, total) = handles.progress();
+    let failed = handles.failed_paths();
+
+    for mut style in &mut q_fill {
+        style.width = Percent(100. * loaded as f32 / total.max(1) as f32);
+    }
+
+    for mut text in &mut q_count_text {
+        text.sections[0].value = format!("{} / {}", loaded, total);
+    }
+
+    for mut style in &mut q_failure {
+        let display = if failed.is_empty() {
+            Display::None
+        } else {
+            Display::Flex
+        };
+
+        if style.display != display {
+            style.display = display;
+        }
+    }
+
+    for mut text in &mut q_failure_text {
+        let value = format!("Couldn't load:\n{}", failed.join("\n"));
+
+        if !failed.is_empty() && text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+}
+
+fn handle_loading_action(
+    handles: AllAssetHandles,
+    mut button_query: InteractionQuery<&LoadingAction>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                LoadingAction::Retry => {
+                    for path in handles.failed_paths() {
+                        handles.asset_server.reload(path);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn all_assets_loaded(handles: AllAssetHandles) -> bool {
+    handles.all_loaded()
 }
 
 fn continue_to_title(mut next_screen: ResMut<NextState<Screen>>) {
@@ -1,21 +1,27 @@
 //! The game's main screen states and transitions between them.
 
 mod credits;
+mod load_game;
 mod loading;
 pub mod playing;
 pub mod title;
+mod victory;
 
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
     app.init_state::<Screen>();
     app.enable_state_scoped_entities::<Screen>();
+    app.add_computed_state::<InRun>();
+    app.enable_state_scoped_entities::<InRun>();
 
     app.add_plugins((
         loading::plugin,
         title::plugin,
         credits::plugin,
+        load_game::plugin,
         playing::plugin,
+        victory::plugin,
     ));
 }
 
@@ -26,5 +32,20 @@ pub enum Screen {
     Loading,
     Title,
     Credits,
+    LoadGame,
     Playing,
+    Victory,
+}
+
+/// Active for as long as a run is going, including while the victory screen is up.
+/// Anything that should outlive a trip to the victory screen and back is scoped to this instead of [`Screen::Playing`].
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub struct InRun;
+
+impl ComputedStates for InRun {
+    type SourceStates = Screen;
+
+    fn compute(screen: Screen) -> Option<Self> {
+        matches!(screen, Screen::Playing | Screen::Victory).then_some(InRun)
+    }
 }
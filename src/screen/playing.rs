@@ -10,28 +10,42 @@ use bevy::{
     },
     math::VectorSpace,
     prelude::*,
-    sprite::MaterialMesh2dBundle,
 };
 use bevy_tweening::{lens::TransformPositionLens, Animator, EaseFunction, Tween};
 use num_bigint::BigUint;
 use rand::Rng;
 
-use super::Screen;
+use super::{InRun, Screen};
 use crate::{
     game::{
+        achievements::Achievements,
         assets::{FontKey, HandleMap, SfxKey, SoundtrackKey},
-        audio::soundtrack::{PlaySfx, PlaySoundtrack},
-        materials::materials::{RingMaterial, SocketMaterial, SocketUiMaterial},
+        audio::{
+            mixer::AudioBus,
+            music::{Instrument, PlayNote},
+            soundtrack::{PlaySoundtrack, SoundtrackIntensity},
+        },
+        materials::{
+            materials::{SocketMaterial, SocketUiMaterial, LONG_AGO_SECONDS},
+            socket_instances::{SocketInstance, SocketInstances},
+        },
+        multiplier::Multiplier,
+        number_format::{format_multiplier, format_number},
+        save::RunSave,
+        settings::{NumberFormat, Settings},
+        spawn::hand::FlashHand,
         spawn::level::{
-            get_grid_coordinates, map_socket_color, map_socket_color_hotkey,
+            get_grid_coordinates, map_socket_color, map_socket_color_chord_tone,
+            map_socket_color_hotkey,
             map_socket_color_trigger_duration, map_socket_highlight_color, CycleTrigger,
-            GameplayMeshes, Ring, RingIndex, Socket, SocketColor, SpawnLevel,
+            Ring, RingIndex, Socket, SocketColor, SpawnLevel,
         },
     },
     ui::{
+        event_log::LogEvent,
         hotbar::map_socket_color_description_text,
         shop::{
-            multiply_biguint_with_float, EnhanceColorUpgrade, NewShop, UpgradeHistory, UpgradeKind,
+            EnhanceColorUpgrade, NewShop, UpgradeHistory, UpgradeKind,
         },
     },
 };
@@ -45,6 +59,8 @@ pub struct Currency {
 }
 
 pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(InRun), (apply_run_start, enter_run).chain());
+    app.add_systems(OnExit(InRun), reset_run);
     app.add_systems(OnEnter(Screen::Playing), enter_playing);
     app.add_systems(OnExit(Screen::Playing), exit_playing);
 
@@ -52,34 +68,95 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         ((
             count_blue_orbs,
+            count_play_time,
             (progress_cycle, ring_cycle_display).chain(),
             despawn_after_system,
-            update_socket_material_time,
+            select_soundtrack_intensity.run_if(
+                resource_changed::<RingIndex>.or_else(resource_changed::<UpgradeHistory>),
+            ),
         )
             .run_if(in_state(Screen::Playing)),),
     );
 
     app.init_resource::<Currency>();
     app.init_resource::<BlueOrbCount>();
+    app.init_resource::<RunStats>();
+    app.init_resource::<RunStart>();
 
     app.observe(on_socket_triggered);
     app.observe(on_cycle_complete);
 }
 
-fn enter_playing(
+/// Totals for the current run, for the victory screen.
+#[derive(Resource, Default)]
+pub struct RunStats {
+    /// Only counts time spent in [`Screen::Playing`].
+    pub play_seconds: f32,
+    pub total_earned: BigUint,
+    /// Set once the player keeps going after winning.
+    pub endless: bool,
+    /// Set once the dev console changes anything in the run. Cheated runs don't count towards
+    /// records or achievements.
+    pub cheated: bool,
+}
+
+/// How the next run begins. The title screen sets this before starting one.
+#[derive(Resource, Default)]
+pub enum RunStart {
+    #[default]
+    New,
+    /// Pick a saved run back up.
+    Load(RunSave),
+    /// Start with enough money to buy anything, for trying things out.
+    #[cfg(feature = "dev")]
+    Debug,
+}
+
+/// Puts back what a saved run had, before anything in the run is spawned from it.
+fn apply_run_start(
+    run_start: Res<RunStart>,
+    mut currency: ResMut<Currency>,
+    mut upgrade_history: ResMut<UpgradeHistory>,
+    mut run_stats: ResMut<RunStats>,
+) {
+    match &*run_start {
+        RunStart::New => {}
+        RunStart::Load(save) => {
+            currency.amount = save.currency();
+            upgrade_history.history = save.upgrades.iter().copied().collect();
+            *run_stats = RunStats {
+                play_seconds: save.play_seconds,
+                total_earned: save.total_earned(),
+                endless: save.endless,
+                cheated: save.cheated,
+            };
+        }
+        #[cfg(feature = "dev")]
+        RunStart::Debug => {
+            // more than even the win costs
+            currency.amount = BigUint::from(10u32).pow(101);
+            run_stats.cheated = true;
+        }
+    }
+}
+
+fn count_play_time(mut run_stats: ResMut<RunStats>, time: Res<Time<Virtual>>) {
+    run_stats.play_seconds += time.delta_seconds();
+}
+
+fn enter_run(
     mut commands: Commands,
     mut materials: ResMut<Assets<SocketUiMaterial>>,
     font_handles: ResMut<HandleMap<FontKey>>,
     upgrade_history: Res<UpgradeHistory>,
 ) {
     commands.trigger(SpawnLevel);
-    commands.trigger(PlaySoundtrack::Key(SoundtrackKey::Gameplay));
 
     let mut gameplay_wrapper = Entity::PLACEHOLDER;
 
     commands
         .ui_root()
-        .insert(StateScoped(Screen::Playing))
+        .insert(StateScoped(InRun))
         .with_children(|root_children| {
             let mut gameplay_wrapper_commands =
                 root_children.horizontal_container(JustifyContent::Start, AlignItems::Start);
@@ -93,37 +170,36 @@ fn enter_playing(
                         score_and_hotbar_wrapper
                             .score_display(font_handles[&FontKey::Default].clone());
 
-                        let hotbar_first_position_socket_color = SocketColor::BLUE;
+                        // a loaded run already has some colors bought
+                        let mut hotbar_colors: Vec<SocketColor> = upgrade_history
+                            .history
+                            .iter()
+                            .filter_map(|upgrade| match upgrade {
+                                UpgradeKind::AddColor(upgrade) => Some(upgrade.color),
+                                _ => None,
+                            })
+                            .collect();
+                        hotbar_colors.push(SocketColor::BLUE);
+                        hotbar_colors.sort_by_key(|color| map_socket_color_hotkey(*color));
+
+                        // need to be different materials, even though right now they have the same values
+                        let mut socket_ui_material = |socket_color: SocketColor| {
+                            materials.add(SocketUiMaterial {
+                                bevel_color: BLACK.into(),
+                                inserted_color: map_socket_color(socket_color),
+                                data: Vec4::new(
+                                    (socket_color as u8).saturating_sub(1) as f32,
+                                    0.,
+                                    0.,
+                                    0.,
+                                ),
+                            })
+                        };
+
                         score_and_hotbar_wrapper
                             .vertical_container(JustifyContent::End, Val::Px(0.))
                             .with_children(|hotbar_wrapper_children| {
-                                // need to be different materials, even though right now they have the same values
-                                let socket_color =
-                                    map_socket_color(hotbar_first_position_socket_color);
-
-                                let button_socket_material = materials.add(SocketUiMaterial {
-                                    bevel_color: BLACK.into(),
-                                    inserted_color: socket_color,
-                                    data: Vec4::new(
-                                        (hotbar_first_position_socket_color as u8).saturating_sub(1)
-                                            as f32,
-                                        0.,
-                                        0.,
-                                        0.,
-                                    ),
-                                });
-
-                                let description_socket_material = materials.add(SocketUiMaterial {
-                                    bevel_color: BLACK.into(),
-                                    inserted_color: socket_color,
-                                    data: Vec4::new(
-                                        (hotbar_first_position_socket_color as u8).saturating_sub(1)
-                                            as f32,
-                                        0.,
-                                        0.,
-                                        0.,
-                                    ),
-                                });
+                                let hotbar_first_position_socket_color = hotbar_colors[0];
 
                                 hotbar_wrapper_children.hotbar_description(
                                     map_socket_color_description_text(
@@ -132,20 +208,21 @@ fn enter_playing(
                                     ),
                                     hotbar_first_position_socket_color,
                                     font_handles[&FontKey::Default].clone(),
-                                    description_socket_material.clone(),
+                                    socket_ui_material(hotbar_first_position_socket_color),
                                 );
 
-                                let hotkey =
-                                    map_socket_color_hotkey(hotbar_first_position_socket_color);
-
                                 hotbar_wrapper_children
-                                    .hotbar(vec![hotbar_first_position_socket_color])
+                                    .hotbar(hotbar_colors.clone())
                                     .with_children(|hotbar_children| {
-                                        hotbar_children.hotbar_button(
-                                            button_socket_material.clone(),
-                                            format!("{}.", hotkey),
-                                            hotkey - 1,
-                                        ); // someday we will have real hotkeys
+                                        for socket_color in &hotbar_colors {
+                                            let hotkey = map_socket_color_hotkey(*socket_color);
+
+                                            hotbar_children.hotbar_button(
+                                                socket_ui_material(*socket_color),
+                                                format!("{}.", hotkey),
+                                                hotkey - 1,
+                                            ); // someday we will have real hotkeys
+                                        }
                                     });
                             });
                     });
@@ -157,79 +234,135 @@ fn enter_playing(
     });
 }
 
+fn enter_playing(mut commands: Commands) {
+    commands.trigger(PlaySoundtrack::Key(SoundtrackKey::Gameplay));
+}
+
+/// Everything scoped to [`InRun`] is despawned on its own. This puts the rest back how a new run expects it.
+fn reset_run(
+    mut ring_index: ResMut<RingIndex>,
+    mut currency: ResMut<Currency>,
+    mut upgrade_history: ResMut<UpgradeHistory>,
+    mut blue_orb_count: ResMut<BlueOrbCount>,
+    mut run_stats: ResMut<RunStats>,
+    mut run_start: ResMut<RunStart>,
+) {
+    ring_index.rings.clear();
+    *currency = Currency::default();
+    *upgrade_history = UpgradeHistory::default();
+    *blue_orb_count = BlueOrbCount::default();
+    *run_stats = RunStats::default();
+    *run_start = RunStart::default();
+}
+
+/// Starts a new run without leaving it first, e.g. from the victory screen.
+/// [`InRun`] doesn't change, so its transitions are run here by hand.
+pub fn restart_run(world: &mut World) {
+    let scoped: Vec<Entity> = world
+        .query_filtered::<Entity, With<StateScoped<InRun>>>()
+        .iter(world)
+        .collect();
+
+    for entity in scoped {
+        // children that were scoped too are already gone
+        if let Some(entity) = world.get_entity_mut(entity) {
+            entity.despawn_recursive();
+        }
+    }
+
+    world.run_schedule(OnExit(InRun));
+    world.run_schedule(OnEnter(InRun));
+}
+
 fn exit_playing(mut commands: Commands) {
     // We could use [`StateScoped`] on the sound playing entites instead.
     commands.trigger(PlaySoundtrack::Disable);
 }
 
+/// The soundtrack picks up as the player builds out more rings and buys more upgrades.
+fn select_soundtrack_intensity(
+    ring_index: Res<RingIndex>,
+    upgrade_history: Res<UpgradeHistory>,
+    mut intensity: ResMut<SoundtrackIntensity>,
+) {
+    let ring_count = ring_index.rings.len();
+    let upgrade_count = upgrade_history.history.len();
+
+    let new_intensity = if ring_count >= 9 || upgrade_count >= 40 {
+        SoundtrackIntensity::Frantic
+    } else if ring_count >= 3 || upgrade_count >= 15 {
+        SoundtrackIntensity::Busy
+    } else {
+        SoundtrackIntensity::Calm
+    };
+
+    intensity.set_if_neq(new_intensity);
+}
+
+/// The panel above each ring showing what it's triggered so far this cycle.
+/// All of a ring's panels are drawn together as one batch of socket instances.
 #[derive(Component)]
-pub struct CycleDisplayPanel;
+pub struct CycleDisplay;
 
 const DISPLAY_MAX_COUNT: usize = 100;
 
 fn ring_cycle_display(
     mut commands: Commands,
-    mut q_ring: Query<(Entity, &mut Ring)>,
-    mut q_cycle_display: Query<(Entity, &mut Transform), With<CycleDisplayPanel>>,
-    gameplay_meshes: Res<GameplayMeshes>,
-    mut socket_materials: ResMut<Assets<SocketMaterial>>,
+    mut q_ring: Query<(Entity, &mut Ring), Changed<Ring>>,
+    mut q_cycle_display: Query<&mut SocketInstances, With<CycleDisplay>>,
 ) {
     let row_size = 8;
     let quad_size = 32.;
 
     for (ring_entity, mut ring) in q_ring.iter_mut() {
-        if ring.cycle.len() > ring.cycle_display_panels.len() && ring.cycle.len() < DISPLAY_MAX_COUNT {
-            // reposition existing panels
-            for (i, panel_entity) in ring.cycle_display_panels.iter().enumerate() {
-                let (_e, mut panel_transform) = q_cycle_display.get_mut(*panel_entity).unwrap();
-                panel_transform.translation =
-                    ring_cycle_display_panel_position(i, row_size, quad_size, ring.cycle.len())
-                        .extend(1.);
-            }
+        let panel_count = ring.cycle.len().min(DISPLAY_MAX_COUNT);
 
-            // spawn new panels
-            let start_index = ring.cycle_display_panels.len();
-            let end_index = ring.cycle.len();
-
-            for i in start_index..end_index {
-                let trigger = ring.cycle[i];
-
-                commands.entity(ring_entity).with_children(|ring_children| {
-                    let cycle_display_entity = ring_children
-                        .spawn((
-                            MaterialMesh2dBundle {
-                                mesh: gameplay_meshes.quad32.clone(),
-                                material: socket_materials.add(SocketMaterial {
-                                    inserted_color: map_socket_color(trigger.color),
-                                    highlight_color: map_socket_highlight_color(trigger.color),
-                                    bevel_color: { BLACK.into() },
-                                    data: Vec4::new(
-                                        -1.,
-                                        map_socket_color_trigger_duration(trigger.color),
-                                        0.,
-                                        (trigger.color as u8).saturating_sub(1) as f32,
-                                    ),
-                                    data2: Vec4::new(100000000000000000., 0., 0., 0.),
-                                }),
-                                transform: Transform::from_translation(
-                                    ring_cycle_display_panel_position(
-                                        i,
-                                        row_size,
-                                        quad_size,
-                                        ring.cycle.len(),
-                                    )
-                                    .extend(1.),
-                                ),
-                                ..default()
-                            },
-                            CycleDisplayPanel,
-                        ))
-                        .id();
+        let existing_instances = ring
+            .cycle_display
+            .and_then(|entity| q_cycle_display.get_mut(entity).ok());
 
-                    ring.cycle_display_panels.push_back(cycle_display_entity);
-                });
-            }
+        if existing_instances
+            .as_ref()
+            .is_some_and(|instances| instances.0.len() == panel_count)
+        {
+            continue;
+        }
+
+        let panels = ring.cycle[..panel_count]
+            .iter()
+            .enumerate()
+            .map(|(i, trigger)| SocketInstance {
+                position: ring_cycle_display_panel_position(i, row_size, quad_size, panel_count),
+                size: quad_size,
+                inserted_color: map_socket_color(trigger.color),
+                highlight_color: map_socket_highlight_color(trigger.color),
+                bevel_color: BLACK.into(),
+                // shown as if they triggered forever ago, so they're filled in and unlit
+                data: Vec4::new(
+                    LONG_AGO_SECONDS,
+                    map_socket_color_trigger_duration(trigger.color),
+                    LONG_AGO_SECONDS,
+                    (trigger.color as u8).saturating_sub(1) as f32,
+                ),
+            })
+            .collect();
+
+        if let Some(mut instances) = existing_instances {
+            instances.0 = panels;
+            continue;
         }
+
+        let cycle_display_entity = commands
+            .spawn((
+                Name::new("Cycle Display"),
+                SpatialBundle::from_transform(Transform::from_xyz(0., 0., 1.)),
+                SocketInstances(panels),
+                CycleDisplay,
+            ))
+            .set_parent(ring_entity)
+            .id();
+
+        ring.cycle_display = Some(cycle_display_entity);
     }
 }
 
@@ -276,16 +409,25 @@ pub enum TriggerSource {
 
 #[derive(Event)]
 pub struct SocketTriggered {
-    socket: usize,
-    ring: Entity,
-    bonus_factor: f32,
-    source: TriggerSource,
+    pub socket: usize,
+    pub ring: Entity,
+    pub bonus_factor: Multiplier,
+    pub source: TriggerSource,
 }
 
 #[derive(Event)]
 pub struct CycleComplete {
-    ring: Entity,
-    new_cycle_start_seconds: f32,
+    pub ring: Entity,
+    pub new_cycle_start_seconds: f32,
+}
+
+/// Triggered once a completed cycle has been paid out and the ring is ready for the next one.
+#[derive(Event)]
+pub struct CyclePaidOut {
+    pub ring: Entity,
+    pub payout: BigUint,
+    /// The cycle multiplier the payout was made with, before it was reset.
+    pub multiplier: Multiplier,
 }
 
 pub struct ReduceCooldownEffect {
@@ -336,8 +478,8 @@ fn on_socket_triggered(
     blue_orb_count: ResMut<BlueOrbCount>,
     currency: Res<Currency>,
     time: Res<Time>,
+    settings: Res<Settings>,
 ) {
-    let ring_count = q_ring.iter().count();
     let all_ring_previous_socket_count = q_ring
         .iter()
         .fold(0, |acc, (_, ring, _)| acc + ring.previous_cycle.len());
@@ -380,16 +522,24 @@ fn on_socket_triggered(
                 < time.elapsed_seconds());
 
         if triggered_successfully {
-            let bonus_factor = trigger.event().bonus_factor;
+            let bonus_factor = &trigger.event().bonus_factor;
+
+            let keys = [SfxKey::Click, SfxKey::Click2];
+            let random_index = rand::thread_rng().gen_range(0..keys.len());
 
-            if ring_count == 1 {
-                // no clicks when there are multiple rings
-                let keys = [SfxKey::Click, SfxKey::Click2];
-                let random_index = rand::thread_rng().gen_range(0..keys.len());
+            commands.trigger(PlayNote {
+                instrument: Instrument::Click(keys[random_index]),
+                chord_tone: map_socket_color_chord_tone(socket.color),
+                bus: AudioBus::Sfx,
+                volume: 0.5,
+                position: Some(ring_transform.translation + socket_transform.translation),
+            });
 
-                commands.trigger(PlaySfx {
-                    key: keys[random_index],
-                    volume: 0.5,
+            // only the hand sweeping past counts, not sockets set off by other sockets
+            if trigger.event().source == TriggerSource::Cycle {
+                commands.trigger(FlashHand {
+                    ring: ring_entity,
+                    color: socket.color,
                 });
             }
 
@@ -408,37 +558,30 @@ fn on_socket_triggered(
                             commands.trigger(SocketTriggered {
                                 ring: soft_trigger.ring,
                                 socket: soft_trigger.socket_index,
-                                bonus_factor: 1.,
+                                bonus_factor: Multiplier::one(),
                                 source: TriggerSource::Blue,
                             })
                         }
-                        ring.cycle_score += multiply_biguint_with_float(
-                            &BigUint::from(blue_orb_count.0 * 6),
-                            bonus_factor,
-                        );
+                        ring.cycle_score +=
+                            bonus_factor.apply(&BigUint::from(blue_orb_count.0 * 6));
                     } else if upgrade_history.history.contains(&UpgradeKind::EnhanceColor(
                         EnhanceColorUpgrade {
                             color: SocketColor::BLUE,
                             tier: 2,
                         },
                     )) {
-                        ring.cycle_score += multiply_biguint_with_float(
-                            &BigUint::from(blue_orb_count.0 * 4),
-                            bonus_factor,
-                        );
+                        ring.cycle_score +=
+                            bonus_factor.apply(&BigUint::from(blue_orb_count.0 * 4));
                     } else if upgrade_history.history.contains(&UpgradeKind::EnhanceColor(
                         EnhanceColorUpgrade {
                             color: SocketColor::BLUE,
                             tier: 1,
                         },
                     )) {
-                        ring.cycle_score += multiply_biguint_with_float(
-                            &BigUint::from(blue_orb_count.0 * 2),
-                            bonus_factor,
-                        );
-                    } else {
                         ring.cycle_score +=
-                            multiply_biguint_with_float(&BigUint::from(1u32), bonus_factor);
+                            bonus_factor.apply(&BigUint::from(blue_orb_count.0 * 2));
+                    } else {
+                        ring.cycle_score += bonus_factor.apply(&BigUint::from(1u32));
                     }
                 }
                 SocketColor::RED => {
@@ -463,33 +606,30 @@ fn on_socket_triggered(
                             commands.trigger(SocketTriggered {
                                 socket: prev_index,
                                 ring: ring_entity,
-                                bonus_factor: 2. * bonus_factor,
+                                bonus_factor: &Multiplier::from_integer(2u32) * bonus_factor,
                                 source
                             });
                             commands.trigger(SocketTriggered {
                                 socket: next_index,
                                 ring: ring_entity,
-                                bonus_factor: 2. * bonus_factor,
+                                bonus_factor: &Multiplier::from_integer(2u32) * bonus_factor,
                                 source
                             });
                         } else {
                             commands.trigger(SocketTriggered {
                                 socket: prev_index,
                                 ring: ring_entity,
-                                bonus_factor: 1.,
+                                bonus_factor: Multiplier::one(),
                                 source
                             });
                             commands.trigger(SocketTriggered {
                                 socket: next_index,
                                 ring: ring_entity,
-                                bonus_factor: 1.,
+                                bonus_factor: Multiplier::one(),
                                 source
                             });
                         }
-                    } else {
-                        println!("it literally wasn't lmfao");
                     }
-
                 }
                 SocketColor::GREEN => {
                     if upgrade_history.history.contains(&UpgradeKind::EnhanceColor(
@@ -504,12 +644,12 @@ fn on_socket_triggered(
                             ring_index: ring.index,
                             soft: false,
                         }) {
-                            5.
+                            5u32
                         } else {
-                            1.
+                            1u32
                         };
 
-                        if retrigger_mult != 1. {
+                        if retrigger_mult != 1 && settings.scrolling_text.shows_details() {
                             spawn_scrolling_text(
                                 &mut commands,
                                 format!("Retrigger! (x5)"),
@@ -523,15 +663,17 @@ fn on_socket_triggered(
                                 time.elapsed_seconds(),
                                 font_handles[&FontKey::Default].clone(),
                                 26.,
+                                settings.reduce_motion,
                             );
                         }
 
-                        let score_gained =
-                            all_ring_previous_socket_count as f32 * retrigger_mult * bonus_factor;
-                        ring.cycle_score += score_gained as u32;
+                        ring.cycle_score += bonus_factor.apply(
+                            &(BigUint::from(all_ring_previous_socket_count) * retrigger_mult),
+                        );
                     } else {
-                        let score_gained = ring.previous_cycle.len() as f32 * bonus_factor;
-                        ring.cycle_score += score_gained as u32;
+                        let score_gained =
+                            bonus_factor.apply(&BigUint::from(ring.previous_cycle.len()));
+                        ring.cycle_score += score_gained;
                     }
                 }
                 SocketColor::ORANGE => {
@@ -544,21 +686,21 @@ fn on_socket_triggered(
                         pending_socket_effects.push(SocketEffect::ReduceCooldown(
                             ReduceCooldownEffect {
                                 ring: ring_entity,
-                                amount: 1.0 * bonus_factor,
+                                amount: 1.0 * bonus_factor.to_f32(),
                             },
                         ));
                     } else {
                         pending_socket_effects.push(SocketEffect::ReduceCooldown(
                             ReduceCooldownEffect {
                                 ring: ring_entity,
-                                amount: 0.5 * bonus_factor,
+                                amount: 0.5 * bonus_factor.to_f32(),
                             },
                         ));
                     }
                 }
                 SocketColor::NONE => panic!("Shouldn't get points for an empty socket."),
                 SocketColor::PINK => {
-                    ring.cycle_multiplier += 1. * bonus_factor;
+                    ring.cycle_multiplier += bonus_factor;
                 }
             }
 
@@ -569,8 +711,7 @@ fn on_socket_triggered(
                 soft: false,
             });
 
-            ring.pending_amount =
-                multiply_biguint_with_float(&ring.cycle_score, ring.cycle_multiplier); // TODO: have an update_currency_system that correctly updates pending...
+            ring.pending_amount = ring.cycle_multiplier.apply(&ring.cycle_score); // TODO: have an update_currency_system that correctly updates pending...
 
             let socket_material = materials.get_mut(socket_mat_handle).unwrap();
 
@@ -579,12 +720,15 @@ fn on_socket_triggered(
             socket.last_triggered_time_seconds = time.elapsed_seconds();
 
             let score_diff = &ring.cycle_score - old_score;
-            let mult_diff = &ring.cycle_multiplier - old_multiplier;
+            socket.earned_this_cycle += &score_diff;
+            let mult_diff = ring.cycle_multiplier.saturating_sub(&old_multiplier);
+
+            let show_text = settings.scrolling_text.shows_details();
 
-            if score_diff != BigUint::ZERO {
+            if show_text && score_diff != BigUint::ZERO {
                 spawn_scrolling_text(
                     &mut commands,
-                    format!("+${}", format_scientific(&score_diff)),
+                    format!("+${}", format_number(&score_diff, settings.number_format)),
                     ring_transform.translation + (socket_transform.translation.xy()).extend(100.),
                     1.,
                     100.,
@@ -593,11 +737,12 @@ fn on_socket_triggered(
                     time.elapsed_seconds(),
                     font_handles[&FontKey::Default].clone(),
                     20.,
+                    settings.reduce_motion,
                 );
-            } else if mult_diff != 0. {
+            } else if show_text && !mult_diff.is_zero() {
                 spawn_scrolling_text(
                     &mut commands,
-                    format!("+{}x", mult_diff),
+                    format!("+{}x", format_multiplier(&mult_diff, settings.number_format)),
                     ring_transform.translation + (socket_transform.translation.xy()).extend(100.),
                     1.,
                     100.,
@@ -606,6 +751,7 @@ fn on_socket_triggered(
                     time.elapsed_seconds(),
                     font_handles[&FontKey::Default].clone(),
                     26.,
+                    settings.reduce_motion,
                 );
             }
         }
@@ -667,9 +813,13 @@ fn on_cycle_complete(
     trigger: Trigger<CycleComplete>,
     mut commands: Commands,
     mut q_ring: Query<(&mut Ring, &Transform)>,
+    mut q_socket: Query<&mut Socket>,
     mut currency: ResMut<Currency>,
     font_handles: ResMut<HandleMap<FontKey>>,
     time: Res<Time>,
+    settings: Res<Settings>,
+    achievements: Res<Achievements>,
+    mut run_stats: ResMut<RunStats>,
 ) {
     let (mut ring, ring_transform) = q_ring
         .get_mut(trigger.event().ring)
@@ -681,76 +831,98 @@ fn on_cycle_complete(
         .iter()
         .fold(BigUint::ZERO, |acc, bonus| acc + score_bonus(bonus));
 
-    let cycle_score =
-        multiply_biguint_with_float(&(ring.cycle_score.clone()), ring.cycle_multiplier)
-            + bonus_score;
+    // achievement bonuses apply on top of everything the ring earned this cycle
+    let cycle_score = achievements
+        .payout_multiplier()
+        .apply(&(ring.cycle_multiplier.apply(&ring.cycle_score) + &bonus_score));
 
-    let old_multiplier = ring.cycle_multiplier;
+    let old_multiplier = ring.cycle_multiplier.clone();
     let unmultiplied_score = ring.cycle_score.clone();
 
     ring.cycle_count += BigUint::from(1u32);
     ring.previous_bonuses = bonuses.clone();
     ring.previous_cycle = ring.cycle.clone();
+    ring.previous_cycle_score = cycle_score.clone();
 
     currency.amount += &cycle_score;
+    run_stats.total_earned += &cycle_score;
 
-    ring.cycle = Vec::new();
-    ring.cycle_score = BigUint::ZERO;
-    ring.cycle_start_seconds = trigger.event().new_cycle_start_seconds;
-    ring.cycle_multiplier = 1.;
+    commands.trigger(CyclePaidOut {
+        ring: trigger.event().ring,
+        payout: cycle_score.clone(),
+        multiplier: old_multiplier.clone(),
+    });
 
-    // reset the cycle display
-    for cycle_display_entity in &ring.cycle_display_panels {
-        commands.entity(*cycle_display_entity).despawn_recursive();
+    for socket_entity in &ring.sockets {
+        if let Ok(mut socket) = q_socket.get_mut(*socket_entity) {
+            socket.earned_last_cycle = std::mem::take(&mut socket.earned_this_cycle);
+        }
     }
 
-    ring.cycle_display_panels = vec![].into();
+    // every cycle of a ring tends to earn the same bonuses, so logging them all would push everything else out
+    if bonus_score > ring.best_bonus_score {
+        let texts: Vec<String> = bonuses
+            .iter()
+            .map(|bonus| bonus_text(bonus, settings.number_format))
+            .collect();
+
+        commands.trigger(LogEvent {
+            text: format!("New best bonus: {}", texts.join(", ")),
+            ring: Some(trigger.event().ring),
+        });
+
+        ring.best_bonus_score = bonus_score;
+    }
+
+    ring.cycle = Vec::new();
+    ring.cycle_score = BigUint::ZERO;
+    ring.cycle_start_seconds = trigger.event().new_cycle_start_seconds;
+    ring.cycle_multiplier = Multiplier::one();
 
     // display the change in $ if it was positive
     if cycle_score > BigUint::ZERO {
-        // for the outer rings dont bother playing the cycle finish noises
-        // we should probably make this "only rings in view" but uh
-        // nah
-        if ring.index < 9 {
-            let keys = [
-                SfxKey::CycleC,
-                SfxKey::CycleD,
-                SfxKey::CycleHighF,
-                SfxKey::CycleHighG,
-                SfxKey::CycleLowF,
-                SfxKey::CycleLowG,
-            ];
-
-            let random_index = rand::thread_rng().gen_range(0..keys.len());
+        // each ring sits on its own tone of the chord, so neighbours harmonize instead of doubling up
+        commands.trigger(PlayNote {
+            instrument: Instrument::Chime,
+            chord_tone: ring.index,
+            bus: AudioBus::Sfx,
+            volume: 1.5,
+            position: Some(ring_transform.translation),
+        });
 
-            commands.trigger(PlaySfx {
-                key: keys[random_index],
-                volume: 1.5,
-            });
+        if settings.scrolling_text.shows_totals() {
+            spawn_scrolling_text(
+                &mut commands,
+                format!("+${}", format_number(&cycle_score, settings.number_format)),
+                (ring_transform.translation.xy()).extend(100.) + Vec3::Y * 50.,
+                2.,
+                200.,
+                TextScrollDirection::UP,
+                ORANGE.into(),
+                time.elapsed_seconds(),
+                font_handles[&FontKey::Default].clone(),
+                36.,
+                settings.reduce_motion,
+            );
         }
+    }
 
-        spawn_scrolling_text(
-            &mut commands,
-            format!("+${}", format_scientific(&cycle_score)),
-            (ring_transform.translation.xy()).extend(100.) + Vec3::Y * 50.,
-            2.,
-            200.,
-            TextScrollDirection::UP,
-            ORANGE.into(),
-            time.elapsed_seconds(),
-            font_handles[&FontKey::Default].clone(),
-            36.,
-        );
+    if !settings.scrolling_text.shows_details() {
+        return;
     }
 
     let mut texts_above_bonus = 1;
     // display the multiplier
-    if old_multiplier > 1. {
+    if old_multiplier > Multiplier::one() {
         texts_above_bonus += 1;
 
         spawn_scrolling_text(
             &mut commands,
-            format!("${}x{}", unmultiplied_score, old_multiplier),
+            format!(
+                "${}x{}",
+                format_number(&unmultiplied_score, settings.number_format),
+                format_multiplier(&old_multiplier, settings.number_format)
+            ),
             (ring_transform.translation.xy()).extend(100.)
                 + Vec3::Y * (50. - 25. * texts_above_bonus as f32),
             2.,
@@ -760,6 +932,7 @@ fn on_cycle_complete(
             time.elapsed_seconds(),
             font_handles[&FontKey::Default].clone(),
             20.,
+            settings.reduce_motion,
         );
     }
 
@@ -767,7 +940,7 @@ fn on_cycle_complete(
     for (index, bonus) in bonuses.iter().enumerate() {
         spawn_scrolling_text(
             &mut commands,
-            bonus_text(bonus),
+            bonus_text(bonus, settings.number_format),
             (ring_transform.translation.xy()).extend(100.)
                 + Vec3::Y * (50. - 25. * (texts_above_bonus + index + 1) as f32),
             2.,
@@ -777,15 +950,18 @@ fn on_cycle_complete(
             time.elapsed_seconds(),
             font_handles[&FontKey::Default].clone(),
             16.,
+            settings.reduce_motion,
         );
     }
 }
 
-fn bonus_text(bonus: &CycleBonus) -> String {
+fn bonus_text(bonus: &CycleBonus, number_format: NumberFormat) -> String {
     match bonus {
-        CycleBonus::Overflow(oversized_amount) => {
-            format!("Overflow ({}) +${}", oversized_amount, score_bonus(bonus))
-        }
+        CycleBonus::Overflow(oversized_amount) => format!(
+            "Overflow ({}) +${}",
+            oversized_amount,
+            format_number(&score_bonus(bonus), number_format)
+        ),
     }
 }
 
@@ -811,6 +987,7 @@ fn spawn_scrolling_text(
     current_time: f32,
     font: Handle<Font>, // is it idiomatic to make the caller clone it? lol
     font_size: f32,
+    reduce_motion: bool,
 ) {
     let signed_distance_y = distance
         * match direction {
@@ -834,29 +1011,31 @@ fn spawn_scrolling_text(
         ..default()
     };
 
-    commands.spawn((
+    let mut text_commands = commands.spawn((
         Text2dBundle {
             text: Text::from_section(text, text_style.clone()).with_justify(JustifyText::Center),
             transform: Transform::from_translation(start_position),
             ..default()
         },
-        Animator::new(tween),
         DespawnAfter {
             lifetime_seconds: duration_seconds,
             spawn_time: current_time,
         },
+        StateScoped(InRun),
     ));
+
+    if !reduce_motion {
+        text_commands.insert(Animator::new(tween));
+    }
 }
 
 fn progress_cycle(
     mut commands: Commands,
     q_socket: Query<(Entity, &Socket, &Transform)>,
-    q_ring: Query<(Entity, &mut Ring, &Handle<RingMaterial>)>,
+    mut q_ring: Query<(Entity, &mut Ring)>,
     time: Res<Time>,
-    mut old_progress_pcts: Local<Vec<f32>>,
-    mut ring_materials: ResMut<Assets<RingMaterial>>,
 ) {
-    for (ring_entity, ring, ring_mat_handle) in &q_ring {
+    for (ring_entity, mut ring) in &mut q_ring {
         let seconds_since_cycle_start = time.elapsed_seconds() - ring.cycle_start_seconds;
         let cycle_time_remaining = ring.cycle_duration - seconds_since_cycle_start;
 
@@ -870,21 +1049,12 @@ fn progress_cycle(
 
         let progress_pct = 1. - cycle_time_remaining / ring.cycle_duration;
 
-        let ring_mat = ring_materials
-            .get_mut(ring_mat_handle)
-            .expect("Ring should've had a RingMaterial.");
-        ring_mat.data[2] = progress_pct;
-
         for socket_entity in &ring.sockets {
             let (socket_entity, socket, _t) = q_socket
                 .get(*socket_entity)
                 .expect("Ring's socket Vec contained Entity that was not Socket!");
 
-            if old_progress_pcts.len() < ring.index + 1 {
-                old_progress_pcts.push(0.)
-            }
-
-            let old_progress_pct = old_progress_pcts[ring.index];
+            let old_progress_pct = ring.last_progress_pct;
 
             let socket_position_pct =
                 (ring.sockets.len() as f32 - socket.index as f32) / ring.sockets.len() as f32;
@@ -899,32 +1069,14 @@ fn progress_cycle(
                 commands.trigger(SocketTriggered {
                     socket: socket.index,
                     ring: ring_entity,
-                    bonus_factor: 1.,
+                    bonus_factor: Multiplier::one(),
                     source: TriggerSource::Cycle,
                 });
             }
         }
 
-        old_progress_pcts[ring.index] = progress_pct;
-    }
-}
-
-pub fn update_socket_material_time(
-    mut commands: Commands,
-    mut materials: ResMut<Assets<SocketMaterial>>,
-    q_socket: Query<(&Handle<SocketMaterial>)>,
-    q_ring: Query<(&mut Ring)>,
-    time: Res<Time>,
-) {
-    // it is unacceptable that globals.time wraps in the shader
-    // so we manually pass the real time
-
-    for ring in &q_ring {
-        for entity in &ring.sockets {
-            let handle = q_socket.get(*entity).unwrap();
-            let mat = materials.get_mut(handle).unwrap();
-            mat.data2 = Vec4::new(time.elapsed_seconds(), 0., 0., 0.)
-        }
+        // bookkeeping only, so it shouldn't count as the ring changing every frame
+        ring.bypass_change_detection().last_progress_pct = progress_pct;
     }
 }
 
@@ -946,31 +1098,3 @@ fn despawn_after_system(
         }
     }
 }
-
-pub fn format_scientific(num: &BigUint) -> String {
-    let digits = num.to_str_radix(10);
-    let len = digits.len();
-
-    if len <= 4 {
-        return digits;
-    }
-
-    let mut digits = digits;
-    let original_len = digits.len();
-
-    digits = digits.trim_end_matches('0').to_string();
-    let significant_digits = digits.len();
-
-    if significant_digits == 1 {
-        return format!("{}e{}", digits, original_len - 1);
-    }
-
-    digits.insert(1, '.');
-    let digits_with_precision = if digits.len() > 5 {
-        digits[..5].to_string()
-    } else {
-        digits
-    };
-
-    format!("{}e{}", digits_with_precision, original_len - 1)
-}
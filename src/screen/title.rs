@@ -1,5 +1,5 @@
 use bevy::{
-    color::palettes::{css::{BLACK, WHITE}, tailwind::{GRAY_600, GRAY_700}}, math::VectorSpace, prelude::*, sprite::{MaterialMesh2dBundle, Mesh2dHandle}, window::PrimaryWindow
+    color::palettes::{css::{BLACK, ORANGE, WHITE}, tailwind::{GRAY_400, GRAY_600, GRAY_700, GRAY_800, GRAY_900}}, math::VectorSpace, prelude::*, sprite::{MaterialMesh2dBundle, Mesh2dHandle}, window::PrimaryWindow
 };
 use bevy_mod_picking::{
     events::{Drag, Pointer},
@@ -7,12 +7,12 @@ use bevy_mod_picking::{
     prelude::On,
 };
 
-use super::{playing::update_socket_material_time, Screen};
+use super::{load_game::format_last_played, playing::RunStart, Screen};
 use crate::{
     game::{
-        assets::{FontKey, HandleMap}, materials::materials::{BackgroundMaterial, RingMaterial, SocketMaterial}, spawn::level::{map_socket_color, map_socket_color_trigger_duration, map_socket_highlight_color, socket_position, spawn_ring, spawn_socket, Ring, Socket, SocketColor, RING_RADIUS, RING_THICKNESS}
+        assets::{FontKey, HandleMap}, save::{empty_slot, latest_slot, load_slot, now_unix_seconds, CurrentSaveSlot, SAVE_SLOTS}, records::{format_play_time, Rank, Records}, materials::materials::{BackgroundMaterial, RingMaterial, SocketMaterial}, multiplier::Multiplier, spawn::level::{map_socket_color, map_socket_color_trigger_duration, map_socket_highlight_color, socket_position, spawn_ring, spawn_socket, Ring, Socket, SocketColor, RING_RADIUS, RING_THICKNESS}
     },
-    ui::prelude::*,
+    ui::{achievements::OpenAchievements, prelude::*, settings::OpenSettings},
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -20,22 +20,37 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnExit(Screen::Title), exit_title);
 
     app.register_type::<TitleAction>();
-    app.add_systems(Update, (handle_title_action, cycle_title_ring, update_socket_material_time).run_if(in_state(Screen::Title)));
+    app.add_systems(Update, (handle_title_action, cycle_title_ring).run_if(in_state(Screen::Title)));
 }
 
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
 #[reflect(Component)]
 enum TitleAction {
-    Play,
+    /// Picks up the most recently played save.
+    Continue,
+    NewGame,
+    Load,
+    /// Starts a new run with money to spare, for trying things out.
+    #[cfg(feature = "dev")]
+    DebugStart,
     Credits,
+    Settings,
+    Achievements,
     /// Exit doesn't work well with embedded applications.
     #[cfg(not(target_family = "wasm"))]
     Exit,
+    /// Starts the new run in this slot, over the save that's there.
+    Overwrite(usize),
+    CancelOverwrite,
 }
 
 #[derive(Component)]
 pub struct Background;
 
+/// Asks which save to give up when a new run is started with every slot full.
+#[derive(Component)]
+struct ConfirmOverwriteDialog;
+
 fn enter_title(
     mut commands: Commands,
     mut ring_materials: ResMut<Assets<RingMaterial>>,
@@ -45,6 +60,8 @@ fn enter_title(
     mut q_windows: Query<&mut Window, With<PrimaryWindow>>,
     time: Res<Time>,
     mut socket_materials: ResMut<Assets<SocketMaterial>>,
+    records: Res<Records>,
+    q_background: Query<(), With<Background>>,
 ) {
     let mut window = q_windows.single_mut();
 
@@ -80,6 +97,24 @@ fn enter_title(
                     ),
                 ));
 
+                if let Some(best_seconds) = records.best_seconds {
+                    vertical.spawn((
+                        Name::new("Best Time Text"),
+                        TextBundle::from_section(
+                            format!(
+                                "Best time\n{}\nRank {}",
+                                format_play_time(best_seconds),
+                                Rank::from_seconds(best_seconds).as_str()
+                            ),
+                            TextStyle {
+                                font: font_handles[&FontKey::Default].clone(),
+                                font_size: 16.,
+                                color: ORANGE.into(),
+                            },
+                        ),
+                    ));
+                }
+
                 vertical.spawn((
                     Name::new("Title HorizontalBox"),
                     NodeBundle {
@@ -93,7 +128,16 @@ fn enter_title(
                         ..default()
                     },
                 )).with_children(|horizontal| {
-                    horizontal.button("Play", font_handles[&FontKey::Default].clone()).insert(TitleAction::Play);
+                    if latest_slot().is_some() {
+                        horizontal.button("Continue", font_handles[&FontKey::Default].clone()).insert(TitleAction::Continue);
+                    }
+                    horizontal.button("New Game", font_handles[&FontKey::Default].clone()).insert(TitleAction::NewGame);
+                    horizontal.button("Load", font_handles[&FontKey::Default].clone()).insert(TitleAction::Load);
+                    #[cfg(feature = "dev")]
+                    horizontal.button("Debug Start", font_handles[&FontKey::Default].clone()).insert(TitleAction::DebugStart);
+                    horizontal.button("Settings", font_handles[&FontKey::Default].clone()).insert(TitleAction::Settings);
+                    horizontal.button("Achievements", font_handles[&FontKey::Default].clone()).insert(TitleAction::Achievements);
+                    horizontal.button("Credits", font_handles[&FontKey::Default].clone()).insert(TitleAction::Credits);
     
                     #[cfg(not(target_family = "wasm"))]
                     horizontal.button("Exit", font_handles[&FontKey::Default].clone()).insert(TitleAction::Exit);
@@ -103,18 +147,21 @@ fn enter_title(
 
         });
 
-    commands.spawn((
-        Background,
-        MaterialMesh2dBundle {
-            mesh: Mesh2dHandle(meshes.add(Rectangle::new(1000000.0, 1000000.0))),
-            material: materials.add(BackgroundMaterial {
-                base_color: GRAY_600.into(),
-                blend_color: GRAY_700.into(),
-            }),
-            transform: Transform::from_xyz(0., 0., -999.),
-            ..default()
-        },
-    ));
+    // it outlives the title screen, so coming back here shouldn't add another
+    if q_background.is_empty() {
+        commands.spawn((
+            Background,
+            MaterialMesh2dBundle {
+                mesh: Mesh2dHandle(meshes.add(Rectangle::new(1000000.0, 1000000.0))),
+                material: materials.add(BackgroundMaterial {
+                    base_color: GRAY_600.into(),
+                    blend_color: GRAY_700.into(),
+                }),
+                transform: Transform::from_xyz(0., 0., -999.),
+                ..default()
+            },
+        ));
+    }
 
     let ring_entity = commands
         .spawn((
@@ -171,7 +218,6 @@ fn enter_title(
                             0.,
                             (socket_color as u8).saturating_sub(1) as f32,
                         ),
-                        data2: Vec4::ZERO,
                     }),
                     socket_position(i, num_sockets).extend(1.),
                 );
@@ -184,7 +230,7 @@ fn enter_title(
         sockets: starting_sockets,
         cycle_duration: 4.,
         cycle_start_seconds: time.elapsed_seconds(),
-        cycle_multiplier: 1.,
+        cycle_multiplier: Multiplier::one(),
         index: 0,
         ..default()
     });
@@ -193,13 +239,11 @@ fn enter_title(
 fn cycle_title_ring(
     mut commands: Commands,
     q_socket: Query<(Entity, &Socket, &Transform, &Handle<SocketMaterial>)>,
-    mut q_ring: Query<(Entity, &mut Ring, &Handle<RingMaterial>)>,
+    mut q_ring: Query<(Entity, &mut Ring)>,
     time: Res<Time>,
-    mut old_progress_pcts: Local<Vec<f32>>,
-    mut ring_materials: ResMut<Assets<RingMaterial>>,
     mut socket_materials: ResMut<Assets<SocketMaterial>>,
 ) {
-    for (ring_entity, mut ring, ring_mat_handle) in q_ring.iter_mut() {
+    for (ring_entity, mut ring) in q_ring.iter_mut() {
         let seconds_since_cycle_start = time.elapsed_seconds() - ring.cycle_start_seconds;
         let cycle_time_remaining = ring.cycle_duration - seconds_since_cycle_start;
 
@@ -209,22 +253,12 @@ fn cycle_title_ring(
 
         let progress_pct = 1. - cycle_time_remaining / ring.cycle_duration;
 
-        let ring_mat = ring_materials
-            .get_mut(ring_mat_handle)
-            .expect("Ring should've had a RingMaterial.");
-
-        ring_mat.data[2] = progress_pct;
-        
         for socket_entity in &ring.sockets {
             let (socket_entity, socket, _t, socket_material_handle) = q_socket
                 .get(*socket_entity)
                 .expect("Ring's socket Vec contained Entity that was not Socket!");
 
-            if old_progress_pcts.len() < ring.index + 1 {
-                old_progress_pcts.push(0.)
-            }
-
-            let old_progress_pct = old_progress_pcts[ring.index];
+            let old_progress_pct = ring.last_progress_pct;
 
             let socket_position_pct =
                 (ring.sockets.len() as f32 - socket.index as f32) / ring.sockets.len() as f32;
@@ -242,7 +276,7 @@ fn cycle_title_ring(
             }
         }
 
-        old_progress_pcts[ring.index] = progress_pct;
+        ring.last_progress_pct = progress_pct;
     }
 }
 
@@ -259,21 +293,180 @@ fn exit_title(
 }
 
 fn handle_title_action(
+    mut commands: Commands,
     mut next_screen: ResMut<NextState<Screen>>,
     mut button_query: InteractionQuery<&TitleAction>,
+    mut run_start: ResMut<RunStart>,
+    mut current_slot: ResMut<CurrentSaveSlot>,
+    q_dialog: Query<Entity, With<ConfirmOverwriteDialog>>,
+    font_handles: Res<HandleMap<FontKey>>,
     #[cfg(not(target_family = "wasm"))] mut app_exit: EventWriter<AppExit>,
 ) {
     for (interaction, action) in &mut button_query {
-        if matches!(interaction, Interaction::Pressed) {
-            match action {
-                TitleAction::Play => next_screen.set(Screen::Playing),
-                TitleAction::Credits => next_screen.set(Screen::Credits),
-
-                #[cfg(not(target_family = "wasm"))]
-                TitleAction::Exit => {
-                    app_exit.send(AppExit::Success);
+        if !matches!(interaction, Interaction::Pressed) {
+            continue;
+        }
+
+        // the menu is still under the dialog, and can be pressed through it
+        let is_dialog_action = matches!(
+            action,
+            TitleAction::Overwrite(_) | TitleAction::CancelOverwrite
+        );
+        if !is_dialog_action && !q_dialog.is_empty() {
+            continue;
+        }
+
+        match *action {
+            TitleAction::Continue => {
+                if let Some((slot, save)) = latest_slot() {
+                    *run_start = RunStart::Load(save);
+                    current_slot.0 = slot;
+                    next_screen.set(Screen::Playing);
+                }
+            }
+            TitleAction::NewGame => {
+                *run_start = RunStart::New;
+                start_new_run(&mut commands, &mut next_screen, &mut current_slot, &font_handles);
+            }
+            TitleAction::Load => next_screen.set(Screen::LoadGame),
+            #[cfg(feature = "dev")]
+            TitleAction::DebugStart => {
+                *run_start = RunStart::Debug;
+                start_new_run(&mut commands, &mut next_screen, &mut current_slot, &font_handles);
+            }
+            TitleAction::Credits => next_screen.set(Screen::Credits),
+            TitleAction::Settings => commands.trigger(OpenSettings),
+            TitleAction::Achievements => commands.trigger(OpenAchievements),
+
+            #[cfg(not(target_family = "wasm"))]
+            TitleAction::Exit => {
+                app_exit.send(AppExit::Success);
+            }
+            TitleAction::Overwrite(slot) => {
+                current_slot.0 = slot;
+                next_screen.set(Screen::Playing);
+            }
+            TitleAction::CancelOverwrite => {
+                *run_start = RunStart::default();
+
+                for dialog in &q_dialog {
+                    commands.entity(dialog).despawn_recursive();
                 }
             }
         }
     }
 }
+
+/// A new run goes in an empty slot, or wherever the player says once they're all taken.
+fn start_new_run(
+    commands: &mut Commands,
+    next_screen: &mut NextState<Screen>,
+    current_slot: &mut CurrentSaveSlot,
+    font_handles: &HandleMap<FontKey>,
+) {
+    match empty_slot() {
+        Some(slot) => {
+            current_slot.0 = slot;
+            next_screen.set(Screen::Playing);
+        }
+        None => spawn_confirm_overwrite(commands, font_handles[&FontKey::Default].clone()),
+    }
+}
+
+fn spawn_confirm_overwrite(commands: &mut Commands, font: Handle<Font>) {
+    let style = |font_size: f32, color: Srgba| TextStyle {
+        font: font.clone(),
+        font_size,
+        color: color.into(),
+    };
+    let now = now_unix_seconds();
+
+    commands
+        .spawn((
+            Name::new("Confirm Overwrite"),
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                background_color: Color::srgba(0., 0., 0., 0.6).into(),
+                z_index: ZIndex::Global(100),
+                ..default()
+            },
+            ConfirmOverwriteDialog,
+            StateScoped(Screen::Title),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Name::new("Confirm Overwrite Panel"),
+                NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(8.),
+                        padding: UiRect::all(Val::Px(16.)),
+                        border: UiRect::all(Val::Px(2.)),
+                        ..default()
+                    },
+                    background_color: GRAY_800.into(),
+                    border_color: GRAY_900.into(),
+                    ..default()
+                },
+            ))
+            .with_children(|panel| {
+                panel.spawn(TextBundle::from_section("Every slot is full", style(30., WHITE)));
+                panel.spawn(TextBundle::from_section(
+                    "Pick a save to overwrite. This can't be undone.",
+                    style(16., ORANGE),
+                ));
+
+                for slot in 0..SAVE_SLOTS {
+                    // the file could have gone since the slots were checked
+                    let last_played = load_slot(slot).map_or_else(
+                        || String::from("Empty"),
+                        |save| format!("Last played {}", format_last_played(save.last_played, now)),
+                    );
+
+                    panel
+                        .spawn(NodeBundle {
+                            style: Style {
+                                width: Val::Px(420.),
+                                justify_content: JustifyContent::SpaceBetween,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|row| {
+                            row.spawn(NodeBundle {
+                                style: Style {
+                                    flex_direction: FlexDirection::Column,
+                                    row_gap: Val::Px(4.),
+                                    ..default()
+                                },
+                                ..default()
+                            })
+                            .with_children(|details| {
+                                details.spawn(TextBundle::from_section(
+                                    format!("Slot {}", slot + 1),
+                                    style(20., WHITE),
+                                ));
+                                details.spawn(TextBundle::from_section(last_played, style(14., GRAY_400)));
+                            });
+
+                            row.button("Overwrite", font.clone())
+                                .insert(TitleAction::Overwrite(slot));
+                        });
+                }
+
+                panel
+                    .button("Cancel", font.clone())
+                    .insert(TitleAction::CancelOverwrite);
+            });
+        });
+}
@@ -0,0 +1,240 @@
+//! The screen shown after buying the win, with how the run went.
+//!
+//! The run is only frozen while it's up, so the player can carry on where they left off.
+
+use bevy::{
+    color::palettes::{
+        css::{ORANGE, WHITE},
+        tailwind::{GRAY_400, GRAY_800, GRAY_900},
+    },
+    prelude::*,
+    ui::Val::*,
+};
+use num_bigint::BigUint;
+
+use super::{
+    playing::{restart_run, RunStats},
+    Screen,
+};
+use crate::{
+    game::{
+        achievements::{Achievements, ACHIEVEMENTS},
+        assets::{FontKey, HandleMap, SfxKey},
+        audio::{mixer::AudioBus, soundtrack::PlaySfx},
+        number_format::format_number,
+        records::{format_play_time, Rank, Records},
+        settings::Settings,
+        spawn::level::{Ring, Socket, SocketColor},
+    },
+    ui::{
+        prelude::*,
+        shop::{UpgradeHistory, UpgradeKind},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Victory), enter_victory);
+    app.add_systems(OnExit(Screen::Victory), exit_victory);
+
+    app.add_systems(
+        Update,
+        show_victory_on_win
+            .run_if(in_state(Screen::Playing).and_then(resource_changed::<UpgradeHistory>)),
+    );
+    app.add_systems(
+        Update,
+        handle_victory_action.run_if(in_state(Screen::Victory)),
+    );
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum VictoryAction {
+    KeepPlaying,
+    NewRun,
+}
+
+fn show_victory_on_win(
+    upgrade_history: Res<UpgradeHistory>,
+    run_stats: Res<RunStats>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    if upgrade_history.history.contains(&UpgradeKind::Win) && !run_stats.endless {
+        next_screen.set(Screen::Victory);
+    }
+}
+
+fn enter_victory(
+    mut commands: Commands,
+    mut time: ResMut<Time<Virtual>>,
+    mut records: ResMut<Records>,
+    run_stats: Res<RunStats>,
+    upgrade_history: Res<UpgradeHistory>,
+    achievements: Res<Achievements>,
+    settings: Res<Settings>,
+    q_ring: Query<&Ring>,
+    q_socket: Query<&Socket>,
+    font_handles: Res<HandleMap<FontKey>>,
+) {
+    // nothing in the run moves while the player's looking at this
+    time.pause();
+
+    let seconds = run_stats.play_seconds;
+    let previous_best = records.best_seconds;
+    // console-assisted wins aren't counted
+    let is_best = !run_stats.cheated && records.record_win(seconds);
+    let rank = Rank::from_seconds(seconds);
+
+    let cycles = q_ring
+        .iter()
+        .fold(BigUint::ZERO, |acc, ring| acc + &ring.cycle_count);
+    let sockets: Vec<&Socket> = q_ring
+        .iter()
+        .flat_map(|ring| ring.sockets.iter())
+        .filter_map(|socket| q_socket.get(*socket).ok())
+        .collect();
+    let filled_sockets = sockets
+        .iter()
+        .filter(|socket| socket.color != SocketColor::NONE)
+        .count();
+
+    let best_line = match (is_best, previous_best) {
+        _ if run_stats.cheated => String::from("Console used, so this win isn't recorded"),
+        (true, Some(previous_best)) => format!(
+            "New best time! (was {})",
+            format_play_time(previous_best)
+        ),
+        (true, None) => String::from("First win!"),
+        (false, _) => format!(
+            "Best time: {}",
+            format_play_time(records.best_seconds.unwrap_or(seconds))
+        ),
+    };
+
+    let totals = [
+        format!(
+            "Earned: ${}",
+            format_number(&run_stats.total_earned, settings.number_format)
+        ),
+        format!(
+            "Cycles completed: {}",
+            format_number(&cycles, settings.number_format)
+        ),
+        format!("Rings: {}", q_ring.iter().count()),
+        format!("Sockets filled: {}/{}", filled_sockets, sockets.len()),
+        format!("Upgrades bought: {}", upgrade_history.history.len()),
+        format!(
+            "Achievements: {}/{}",
+            achievements.unlocked.len(),
+            ACHIEVEMENTS.len()
+        ),
+    ];
+
+    let font = font_handles[&FontKey::Default].clone();
+    let style = |font_size: f32, color: Srgba| TextStyle {
+        font: font.clone(),
+        font_size,
+        color: color.into(),
+    };
+
+    commands
+        .spawn((
+            Name::new("Victory Screen"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    height: Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                background_color: Color::srgba(0., 0., 0., 0.6).into(),
+                z_index: ZIndex::Global(100),
+                ..default()
+            },
+            StateScoped(Screen::Victory),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Name::new("Victory Panel"),
+                NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Px(8.),
+                        padding: UiRect::all(Px(24.)),
+                        border: UiRect::all(Px(2.)),
+                        ..default()
+                    },
+                    background_color: GRAY_800.into(),
+                    border_color: GRAY_900.into(),
+                    ..default()
+                },
+            ))
+            .with_children(|panel| {
+                panel.spawn(TextBundle::from_section("You won!", style(40., WHITE)));
+                panel.spawn(TextBundle::from_section(
+                    format!("Rank {}", rank.as_str()),
+                    style(30., ORANGE),
+                ));
+                panel.spawn(TextBundle::from_section(
+                    format!("Completed in {}", format_play_time(seconds)),
+                    style(20., WHITE),
+                ));
+                panel.spawn(TextBundle::from_section(best_line, style(16., ORANGE)));
+
+                for line in totals {
+                    panel.spawn(TextBundle::from_section(line, style(16., GRAY_400)));
+                }
+
+                panel
+                    .spawn(NodeBundle {
+                        style: Style {
+                            column_gap: Px(16.),
+                            margin: UiRect::top(Px(16.)),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|buttons| {
+                        buttons
+                            .button("Keep playing", font.clone())
+                            .insert(VictoryAction::KeepPlaying);
+                        buttons
+                            .button("New run", font.clone())
+                            .insert(VictoryAction::NewRun);
+                    });
+            });
+        });
+
+    commands.trigger(PlaySfx {
+        key: SfxKey::Unlock,
+        bus: AudioBus::Ui,
+        volume: 2.,
+        speed: 0.75,
+        position: None,
+    });
+}
+
+fn exit_victory(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+fn handle_victory_action(
+    mut commands: Commands,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut run_stats: ResMut<RunStats>,
+    mut button_query: InteractionQuery<&VictoryAction>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                VictoryAction::KeepPlaying => run_stats.endless = true,
+                VictoryAction::NewRun => commands.add(restart_run),
+            }
+
+            next_screen.set(Screen::Playing);
+        }
+    }
+}
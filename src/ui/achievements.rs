@@ -0,0 +1,224 @@
+//! The achievements screen, an overlay listing every achievement and what it grants,
+//! and the toast that pops up when one unlocks.
+
+use bevy::{
+    color::palettes::{
+        css::{ORANGE, WHITE},
+        tailwind::{GRAY_400, GRAY_500, GRAY_700, GRAY_800, GRAY_900},
+    },
+    prelude::*,
+    ui::Val::*,
+};
+
+use crate::{
+    game::{
+        achievements::{achievement, AchievementUnlocked, Achievements, ACHIEVEMENTS},
+        assets::{FontKey, HandleMap, SfxKey},
+        audio::{mixer::AudioBus, soundtrack::PlaySfx},
+    },
+    screen::Screen,
+};
+
+use super::{
+    interaction::InteractionQuery,
+    toast::{ShowToast, ToastPriority},
+    widgets::Widgets,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.observe(open_achievements);
+    app.observe(announce_achievement);
+
+    app.add_systems(
+        Update,
+        handle_achievements_action.run_if(any_with_component::<AchievementsMenu>),
+    );
+}
+
+/// Trigger this event to open the achievements overlay on top of the current screen.
+#[derive(Event, Clone)]
+pub struct OpenAchievements;
+
+#[derive(Component)]
+pub struct AchievementsMenu;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum AchievementsAction {
+    Back,
+}
+
+fn announce_achievement(trigger: Trigger<AchievementUnlocked>, mut commands: Commands) {
+    let achievement = achievement(trigger.event().key);
+
+    commands.trigger(ShowToast {
+        title: format!("Achievement: {}", achievement.name),
+        body: achievement.bonus.description(),
+        priority: ToastPriority::High,
+        ..default()
+    });
+
+    commands.trigger(PlaySfx {
+        key: SfxKey::Unlock,
+        bus: AudioBus::Ui,
+        volume: 2.,
+        speed: 1.5,
+        position: None,
+    });
+}
+
+fn open_achievements(
+    _trigger: Trigger<OpenAchievements>,
+    mut commands: Commands,
+    q_achievements_menu: Query<Entity, With<AchievementsMenu>>,
+    screen: Res<State<Screen>>,
+    achievements: Res<Achievements>,
+    font_handles: Res<HandleMap<FontKey>>,
+) {
+    if !q_achievements_menu.is_empty() {
+        return;
+    }
+
+    let font = font_handles[&FontKey::Default].clone();
+
+    commands
+        .spawn((
+            Name::new("Achievements Menu"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    height: Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                background_color: Color::srgba(0., 0., 0., 0.6).into(),
+                z_index: ZIndex::Global(100),
+                ..default()
+            },
+            AchievementsMenu,
+            StateScoped(screen.get().clone()),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Name::new("Achievements Panel"),
+                NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Px(6.),
+                        padding: UiRect::all(Px(16.)),
+                        border: UiRect::all(Px(2.)),
+                        ..default()
+                    },
+                    background_color: GRAY_800.into(),
+                    border_color: GRAY_900.into(),
+                    ..default()
+                },
+            ))
+            .with_children(|panel| {
+                panel.spawn(TextBundle::from_section(
+                    format!(
+                        "Achievements {}/{}",
+                        achievements.unlocked.len(),
+                        ACHIEVEMENTS.len()
+                    ),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 30.,
+                        color: WHITE.into(),
+                    },
+                ));
+
+                panel.spawn(TextBundle::from_section(
+                    format!("Cycle payouts x{}", achievements.payout_multiplier()),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 16.,
+                        color: ORANGE.into(),
+                    },
+                ));
+
+                for achievement in ACHIEVEMENTS.iter() {
+                    let unlocked = achievements.is_unlocked(achievement.key);
+
+                    panel
+                        .spawn((
+                            Name::new("Achievement Row"),
+                            NodeBundle {
+                                style: Style {
+                                    width: Px(420.),
+                                    display: Display::Flex,
+                                    flex_direction: FlexDirection::Column,
+                                    padding: UiRect::all(Px(4.)),
+                                    border: UiRect::all(Px(1.)),
+                                    ..default()
+                                },
+                                background_color: if unlocked {
+                                    GRAY_700.into()
+                                } else {
+                                    GRAY_900.into()
+                                },
+                                border_color: GRAY_400.into(),
+                                ..default()
+                            },
+                        ))
+                        .with_children(|row| {
+                            row.spawn(TextBundle::from_section(
+                                achievement.name,
+                                TextStyle {
+                                    font: font.clone(),
+                                    font_size: 18.,
+                                    color: if unlocked {
+                                        WHITE.into()
+                                    } else {
+                                        GRAY_500.into()
+                                    },
+                                },
+                            ));
+                            row.spawn(TextBundle::from_section(
+                                format!(
+                                    "{} ({})",
+                                    achievement.description,
+                                    achievement.bonus.description()
+                                ),
+                                TextStyle {
+                                    font: font.clone(),
+                                    font_size: 14.,
+                                    color: if unlocked {
+                                        ORANGE.into()
+                                    } else {
+                                        GRAY_500.into()
+                                    },
+                                },
+                            ));
+                        });
+                }
+
+                panel
+                    .button("Back", font.clone())
+                    .insert(AchievementsAction::Back);
+            });
+        });
+}
+
+fn handle_achievements_action(
+    mut commands: Commands,
+    mut button_query: InteractionQuery<&AchievementsAction>,
+    q_achievements_menu: Query<Entity, With<AchievementsMenu>>,
+) {
+    for (interaction, action) in &mut button_query {
+        if !matches!(interaction, Interaction::Pressed) {
+            continue;
+        }
+
+        match action {
+            AchievementsAction::Back => {
+                for menu in &q_achievements_menu {
+                    commands.entity(menu).despawn_recursive();
+                }
+            }
+        }
+    }
+}
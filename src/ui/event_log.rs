@@ -0,0 +1,297 @@
+//! A log of what's happened this run, so nothing that was announced off-screen is missed.
+//!
+//! The panel is opened from the score display. Entries about a ring can be clicked to take
+//! the camera there.
+
+use std::collections::VecDeque;
+
+use bevy::{
+    color::palettes::{
+        css::WHITE,
+        tailwind::{GRAY_400, GRAY_600, GRAY_700, GRAY_800, GRAY_900},
+    },
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+    ui::{FocusPolicy, Val::*},
+};
+use bevy_mod_picking::{
+    events::{Click, Pointer},
+    picking_core::Pickable,
+    prelude::On,
+};
+
+use crate::{
+    game::{
+        assets::{FontKey, HandleMap},
+        camera::{move_camera_to, FOCUS_SCALE},
+        settings::Settings,
+        spawn::level::Ring,
+    },
+    screen::{InRun, Screen},
+};
+
+use super::interaction::InteractionPalette;
+
+const MAX_LOG_ENTRIES: usize = 50;
+const LOG_PANEL_HEIGHT: f32 = 240.;
+const LOG_SCROLL_PIXELS_PER_LINE: f32 = 20.;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<EventLog>();
+
+    app.observe(log_event);
+    app.observe(toggle_event_log);
+    app.observe(focus_log_entry_ring);
+
+    app.add_systems(OnExit(InRun), clear_event_log);
+    app.add_systems(
+        Update,
+        (update_event_log_panel, scroll_event_log)
+            .chain()
+            .run_if(any_with_component::<EventLogPanel>),
+    );
+}
+
+/// Trigger this event to write a line to the event log without showing a toast.
+#[derive(Event)]
+pub struct LogEvent {
+    pub text: String,
+    pub ring: Option<Entity>,
+}
+
+/// Trigger this event to open the event log panel, or close it if it's open.
+#[derive(Event, Clone)]
+pub struct ToggleEventLog;
+
+#[derive(Event)]
+struct FocusLogEntryRing(Entity);
+
+pub struct LogEntry {
+    /// Play time when it happened, so time spent paused doesn't count.
+    pub seconds: f32,
+    pub text: String,
+    pub ring: Option<Entity>,
+}
+
+/// The most recent events, oldest first.
+#[derive(Resource, Default)]
+pub struct EventLog {
+    pub entries: VecDeque<LogEntry>,
+}
+
+#[derive(Component)]
+pub struct EventLogPanel;
+
+/// The list inside the panel, which is moved up and down to scroll.
+#[derive(Component, Default)]
+struct EventLogList {
+    offset: f32,
+}
+
+fn log_event(trigger: Trigger<LogEvent>, mut event_log: ResMut<EventLog>, time: Res<Time<Virtual>>) {
+    if event_log.entries.len() == MAX_LOG_ENTRIES {
+        event_log.entries.pop_front();
+    }
+
+    event_log.entries.push_back(LogEntry {
+        seconds: time.elapsed_seconds(),
+        text: trigger.event().text.clone(),
+        ring: trigger.event().ring,
+    });
+}
+
+fn clear_event_log(mut event_log: ResMut<EventLog>) {
+    event_log.entries.clear();
+}
+
+fn toggle_event_log(
+    _trigger: Trigger<ToggleEventLog>,
+    mut commands: Commands,
+    q_panel: Query<Entity, With<EventLogPanel>>,
+) {
+    if let Ok(panel) = q_panel.get_single() {
+        commands.entity(panel).despawn_recursive();
+        return;
+    }
+
+    commands
+        .spawn((
+            Name::new("Event Log"),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Px(8.),
+                    bottom: Px(8.),
+                    width: Px(340.),
+                    height: Px(LOG_PANEL_HEIGHT),
+                    flex_direction: FlexDirection::Column,
+                    overflow: Overflow::clip_y(),
+                    border: UiRect::all(Px(2.)),
+                    ..default()
+                },
+                background_color: GRAY_800.into(),
+                border_color: GRAY_900.into(),
+                z_index: ZIndex::Global(50),
+                ..default()
+            },
+            // hovering is what lets the mouse wheel scroll the list instead of zooming
+            Interaction::default(),
+            EventLogPanel,
+            StateScoped(Screen::Playing),
+        ))
+        .with_children(|panel| {
+            panel.spawn((
+                Name::new("Event Log List"),
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        ..default()
+                    },
+                    ..default()
+                },
+                Pickable::IGNORE,
+                EventLogList::default(),
+            ));
+        });
+}
+
+fn update_event_log_panel(
+    mut commands: Commands,
+    event_log: Res<EventLog>,
+    q_list: Query<(Entity, Ref<EventLogList>)>,
+    q_ring: Query<&Ring>,
+    font_handles: Res<HandleMap<FontKey>>,
+) {
+    let Ok((list_entity, list)) = q_list.get_single() else {
+        return;
+    };
+
+    if !event_log.is_changed() && !list.is_added() {
+        return;
+    }
+
+    let font = font_handles[&FontKey::Default].clone();
+
+    commands.entity(list_entity).despawn_descendants();
+    commands.entity(list_entity).with_children(|list| {
+        // newest at the top
+        for entry in event_log.entries.iter().rev() {
+            let ring_number = entry
+                .ring
+                .and_then(|ring| q_ring.get(ring).ok())
+                .map(|ring| ring.index + 1);
+
+            let text = match ring_number {
+                Some(ring_number) => format!(
+                    "[{}] {} (ring {})",
+                    format_timestamp(entry.seconds),
+                    entry.text,
+                    ring_number
+                ),
+                None => format!("[{}] {}", format_timestamp(entry.seconds), entry.text),
+            };
+
+            let mut row = list.spawn((
+                Name::new("Event Log Entry"),
+                ButtonBundle {
+                    style: Style {
+                        padding: UiRect::axes(Px(6.), Px(2.)),
+                        ..default()
+                    },
+                    background_color: GRAY_800.into(),
+                    // so the panel underneath still counts as hovered
+                    focus_policy: FocusPolicy::Pass,
+                    ..default()
+                },
+            ));
+
+            row.with_children(|row| {
+                row.spawn((
+                    TextBundle::from_section(
+                        text,
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 14.,
+                            color: if ring_number.is_some() {
+                                WHITE.into()
+                            } else {
+                                GRAY_400.into()
+                            },
+                        },
+                    ),
+                    Pickable::IGNORE,
+                ));
+            });
+
+            if let (Some(ring), Some(_)) = (entry.ring, ring_number) {
+                row.insert((
+                    InteractionPalette {
+                        none: GRAY_800.into(),
+                        hovered: GRAY_700.into(),
+                        pressed: GRAY_600.into(),
+                    },
+                    On::<Pointer<Click>>::commands_mut(move |_, c| {
+                        c.trigger(FocusLogEntryRing(ring))
+                    }),
+                ));
+            }
+        }
+    });
+}
+
+fn scroll_event_log(
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    q_panel: Query<(&Interaction, &Node), With<EventLogPanel>>,
+    mut q_list: Query<(&mut EventLogList, &mut Style, &Node)>,
+) {
+    let Ok((interaction, panel_node)) = q_panel.get_single() else {
+        return;
+    };
+
+    if *interaction != Interaction::Hovered {
+        return;
+    }
+
+    let Ok((mut list, mut style, list_node)) = q_list.get_single_mut() else {
+        return;
+    };
+
+    for event in mouse_wheel_events.read() {
+        let pixels = match event.unit {
+            MouseScrollUnit::Line => event.y * LOG_SCROLL_PIXELS_PER_LINE,
+            MouseScrollUnit::Pixel => event.y,
+        };
+
+        let max_scroll = (list_node.size().y - panel_node.size().y).max(0.);
+        list.offset = (list.offset + pixels).clamp(-max_scroll, 0.);
+        style.top = Px(list.offset);
+    }
+}
+
+fn focus_log_entry_ring(
+    trigger: Trigger<FocusLogEntryRing>,
+    mut commands: Commands,
+    q_ring: Query<&GlobalTransform, With<Ring>>,
+    q_camera: Query<(Entity, &Transform), With<Camera>>,
+    settings: Res<Settings>,
+) {
+    let Ok(ring_transform) = q_ring.get(trigger.event().0) else {
+        return;
+    };
+
+    let (camera_entity, camera_transform) = q_camera.single();
+
+    move_camera_to(
+        &mut commands,
+        camera_entity,
+        camera_transform,
+        ring_transform.translation().xy(),
+        FOCUS_SCALE,
+        settings.reduce_motion,
+    );
+}
+
+fn format_timestamp(seconds: f32) -> String {
+    let seconds = seconds as u32;
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
@@ -1,10 +1,12 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, ui::RelativeCursorPosition};
 
 use crate::game::{assets::SfxKey};
 
+use super::widgets::{Slider, SliderFill};
+
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<InteractionPalette>();
-    app.add_systems(Update, (apply_interaction_palette));
+    app.add_systems(Update, (apply_interaction_palette, (drag_sliders, update_slider_fill).chain()));
 }
 
 pub type InteractionQuery<'w, 's, T> =
@@ -30,4 +32,35 @@ fn apply_interaction_palette(
         }
         .into();
     }
-}
\ No newline at end of file
+}
+
+fn drag_sliders(
+    mut q_slider: Query<(&Interaction, &RelativeCursorPosition, &mut Slider)>,
+) {
+    for (interaction, cursor_position, mut slider) in q_slider.iter_mut() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        if let Some(position) = cursor_position.normalized {
+            let value = position.x.clamp(0., 1.);
+
+            if slider.value != value {
+                slider.value = value;
+            }
+        }
+    }
+}
+
+fn update_slider_fill(
+    q_slider: Query<(&Slider, &Children), Changed<Slider>>,
+    mut q_fill: Query<&mut Style, With<SliderFill>>,
+) {
+    for (slider, children) in &q_slider {
+        for child in children {
+            if let Ok(mut style) = q_fill.get_mut(*child) {
+                style.width = Val::Percent(slider.value * 100.);
+            }
+        }
+    }
+}
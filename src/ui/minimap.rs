@@ -0,0 +1,263 @@
+//! A small map of the ring spiral in the corner of the screen.
+//! Each ring is a dot that glows brighter the more it earns, and the outline shows what the camera can see.
+//! Clicking or dragging on it moves the camera there.
+
+use bevy::{
+    color::palettes::tailwind::{GRAY_600, GRAY_800, GRAY_900},
+    prelude::*,
+    ui::{RelativeCursorPosition, Val::*},
+};
+use bevy_tweening::Animator;
+
+use crate::{
+    game::{
+        camera::{camera_unlocked, ring_bounds},
+        lod::{income_color, income_fraction},
+        spawn::level::{get_ring_world_position, Ring, RingIndex},
+    },
+    screen::{InRun, Screen},
+};
+
+use super::shop::UpgradeHistory;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            spawn_minimap.run_if(resource_changed::<UpgradeHistory>),
+            (
+                sync_minimap_dots,
+                color_minimap_dots,
+                update_minimap_viewport,
+                move_camera_from_minimap,
+            )
+                .chain()
+                .run_if(any_with_component::<Minimap>),
+        )
+            .chain()
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+const MINIMAP_SIZE: f32 = 180.;
+const DOT_SIZE: f32 = 8.;
+
+#[derive(Component)]
+pub struct Minimap;
+
+#[derive(Component)]
+struct MinimapDot {
+    ring: Entity,
+}
+
+#[derive(Component)]
+struct MinimapViewport;
+
+fn spawn_minimap(
+    mut commands: Commands,
+    upgrade_history: Res<UpgradeHistory>,
+    q_minimap: Query<(), With<Minimap>>,
+) {
+    if !camera_unlocked(&upgrade_history) || !q_minimap.is_empty() {
+        return;
+    }
+
+    commands
+        .spawn((
+            Name::new("Minimap"),
+            ButtonBundle {
+                style: Style {
+                    width: Px(MINIMAP_SIZE),
+                    height: Px(MINIMAP_SIZE),
+                    position_type: PositionType::Absolute,
+                    right: Px(8.),
+                    bottom: Px(8.),
+                    border: UiRect::all(Px(2.)),
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+                background_color: GRAY_800.with_alpha(0.85).into(),
+                border_color: GRAY_900.into(),
+                ..default()
+            },
+            RelativeCursorPosition::default(),
+            Minimap,
+            StateScoped(InRun),
+        ))
+        .with_children(|minimap| {
+            minimap.spawn((
+                Name::new("Minimap Viewport"),
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        border: UiRect::all(Px(1.)),
+                        ..default()
+                    },
+                    border_color: Color::WHITE.into(),
+                    ..default()
+                },
+                MinimapViewport,
+            ));
+        });
+}
+
+/// The part of the world the minimap shows: every ring, with some room around them, squared up.
+fn minimap_bounds(ring_index: &RingIndex) -> Rect {
+    let bounds = ring_bounds(ring_index);
+    let side = bounds.size().max_element() * 1.25;
+
+    Rect::from_center_size(bounds.center(), Vec2::splat(side))
+}
+
+/// Where a point in the world is on the minimap, from (0, 0) at the top left to (1, 1) at the bottom right.
+fn world_to_minimap(bounds: Rect, world_position: Vec2) -> Vec2 {
+    let normalized = (world_position - bounds.min) / bounds.size();
+    Vec2::new(normalized.x, 1. - normalized.y)
+}
+
+fn minimap_to_world(bounds: Rect, minimap_position: Vec2) -> Vec2 {
+    bounds.min + Vec2::new(minimap_position.x, 1. - minimap_position.y) * bounds.size()
+}
+
+/// New rings get a dot as they're bought, and every dot moves as the map grows to fit them.
+fn sync_minimap_dots(
+    mut commands: Commands,
+    ring_index: Res<RingIndex>,
+    q_minimap: Query<(Entity, Ref<Minimap>)>,
+    mut q_dots: Query<(&MinimapDot, &mut Style)>,
+) {
+    let Ok((minimap_entity, minimap)) = q_minimap.get_single() else {
+        return;
+    };
+
+    if !ring_index.is_changed() && !minimap.is_added() {
+        return;
+    }
+
+    let bounds = minimap_bounds(&ring_index);
+
+    let dot_style = |coords: IVec2| {
+        let position = world_to_minimap(bounds, get_ring_world_position(coords).xy());
+
+        Style {
+            position_type: PositionType::Absolute,
+            width: Px(DOT_SIZE),
+            height: Px(DOT_SIZE),
+            left: Percent(position.x * 100.),
+            top: Percent(position.y * 100.),
+            margin: UiRect::new(Px(-DOT_SIZE / 2.), Px(0.), Px(-DOT_SIZE / 2.), Px(0.)),
+            ..default()
+        }
+    };
+
+    for (coords, ring_entity) in &ring_index.rings {
+        if let Some((_, mut style)) = q_dots
+            .iter_mut()
+            .find(|(dot, _)| dot.ring == *ring_entity)
+        {
+            *style = dot_style(*coords);
+            continue;
+        }
+
+        commands.entity(minimap_entity).with_children(|minimap| {
+            minimap.spawn((
+                Name::new("Minimap Dot"),
+                NodeBundle {
+                    style: dot_style(*coords),
+                    background_color: GRAY_600.into(),
+                    border_radius: BorderRadius::MAX,
+                    ..default()
+                },
+                MinimapDot { ring: *ring_entity },
+            ));
+        });
+    }
+}
+
+/// Dots brighten with how much their ring paid out last cycle, compared to the best ring.
+fn color_minimap_dots(
+    q_ring: Query<&Ring>,
+    mut q_dots: Query<(&MinimapDot, &mut BackgroundColor)>,
+) {
+    let max_bits = q_ring
+        .iter()
+        .map(|ring| ring.previous_cycle_score.bits())
+        .max()
+        .unwrap_or(0);
+
+    for (dot, mut background_color) in &mut q_dots {
+        let Ok(ring) = q_ring.get(dot.ring) else {
+            continue;
+        };
+
+        let color: Color = income_color(income_fraction(ring, max_bits)).into();
+
+        if background_color.0 != color {
+            background_color.0 = color;
+        }
+    }
+}
+
+fn update_minimap_viewport(
+    ring_index: Res<RingIndex>,
+    q_camera: Query<(&Camera, &Transform)>,
+    mut q_viewport: Query<&mut Style, With<MinimapViewport>>,
+) {
+    let Ok((camera, camera_transform)) = q_camera.get_single() else {
+        return;
+    };
+    let Some(viewport_size) = camera.logical_viewport_size() else {
+        return;
+    };
+
+    let bounds = minimap_bounds(&ring_index);
+    let visible_size = viewport_size * camera_transform.scale.xy();
+    let center = world_to_minimap(bounds, camera_transform.translation.xy());
+    let size = visible_size / bounds.size();
+    let top_left = center - size / 2.;
+
+    for mut style in &mut q_viewport {
+        let (left, top, width, height) = (
+            Percent(top_left.x * 100.),
+            Percent(top_left.y * 100.),
+            Percent(size.x * 100.),
+            Percent(size.y * 100.),
+        );
+
+        if style.left != left || style.top != top || style.width != width || style.height != height {
+            style.left = left;
+            style.top = top;
+            style.width = width;
+            style.height = height;
+        }
+    }
+}
+
+fn move_camera_from_minimap(
+    mut commands: Commands,
+    ring_index: Res<RingIndex>,
+    q_minimap: Query<(&Interaction, &RelativeCursorPosition), With<Minimap>>,
+    mut q_camera: Query<(Entity, &mut Transform), With<Camera>>,
+) {
+    for (interaction, cursor_position) in &q_minimap {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let Some(position) = cursor_position.normalized else {
+            continue;
+        };
+
+        let Ok((camera_entity, mut camera_transform)) = q_camera.get_single_mut() else {
+            return;
+        };
+
+        let world_position = minimap_to_world(minimap_bounds(&ring_index), position);
+
+        // a focus tween in progress would drag the camera right back
+        commands
+            .entity(camera_entity)
+            .remove::<Animator<Transform>>();
+        camera_transform.translation = world_position.extend(camera_transform.translation.z);
+    }
+}
@@ -3,11 +3,19 @@
 // Unused utilities and re-exports may trigger these lints undesirably.
 #![allow(dead_code, unused_imports)]
 
+pub mod achievements;
+pub mod event_log;
 pub mod hotbar;
 pub mod interaction;
+pub mod minimap;
 pub mod palette;
+pub mod pause;
 pub mod scoreboard;
+pub mod settings;
 pub mod shop;
+pub mod shop_tooltip;
+pub mod socket_tooltip;
+pub mod toast;
 pub mod widgets;
 
 pub mod prelude {
@@ -23,8 +31,16 @@ use bevy::prelude::*;
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
         interaction::plugin,
+        minimap::plugin,
         scoreboard::plugin,
+        settings::plugin,
+        pause::plugin,
         hotbar::plugin,
         shop::plugin,
+        shop_tooltip::plugin,
+        toast::plugin,
+        event_log::plugin,
+        socket_tooltip::plugin,
+        achievements::plugin,
     ));
 }
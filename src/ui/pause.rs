@@ -0,0 +1,176 @@
+//! The pause menu, opened with Esc during play.
+//!
+//! Pausing stops virtual time, which everything in a run is timed by, so cycles and cooldowns
+//! pick up exactly where they were instead of catching up all at once.
+
+use bevy::{
+    color::palettes::{
+        css::WHITE,
+        tailwind::{GRAY_800, GRAY_900},
+    },
+    prelude::*,
+    ui::Val::*,
+};
+
+use crate::{
+    game::{
+        assets::{FontKey, HandleMap},
+        save::SaveRun,
+    },
+    screen::Screen,
+};
+
+use super::{
+    achievements::AchievementsMenu,
+    interaction::InteractionQuery,
+    settings::{OpenSettings, SettingsMenu},
+    widgets::Widgets,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnExit(Screen::Playing), unpause);
+
+    app.add_systems(
+        Update,
+        (
+            toggle_pause_on_escape,
+            handle_pause_action.run_if(any_with_component::<PauseMenu>),
+        )
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+#[derive(Component)]
+pub struct PauseMenu;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum PauseAction {
+    Resume,
+    Settings,
+    Save,
+    QuitToTitle,
+}
+
+fn toggle_pause_on_escape(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    q_pause_menu: Query<Entity, With<PauseMenu>>,
+    q_other_menus: Query<(), Or<(With<SettingsMenu>, With<AchievementsMenu>)>>,
+    mut time: ResMut<Time<Virtual>>,
+    font_handles: Res<HandleMap<FontKey>>,
+) {
+    if !keys.just_pressed(KeyCode::Escape) {
+        return;
+    }
+
+    // menus opened on top have their own way back
+    if !q_other_menus.is_empty() {
+        return;
+    }
+
+    if q_pause_menu.is_empty() {
+        time.pause();
+        spawn_pause_menu(&mut commands, font_handles[&FontKey::Default].clone());
+    } else {
+        close_pause_menu(&mut commands, &q_pause_menu, &mut time);
+    }
+}
+
+fn close_pause_menu(
+    commands: &mut Commands,
+    q_pause_menu: &Query<Entity, With<PauseMenu>>,
+    time: &mut Time<Virtual>,
+) {
+    for menu in q_pause_menu {
+        commands.entity(menu).despawn_recursive();
+    }
+
+    time.unpause();
+}
+
+fn spawn_pause_menu(commands: &mut Commands, font: Handle<Font>) {
+    commands
+        .spawn((
+            Name::new("Pause Menu"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    height: Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                background_color: Color::srgba(0., 0., 0., 0.6).into(),
+                // below settings, which can be opened from here
+                z_index: ZIndex::Global(90),
+                ..default()
+            },
+            PauseMenu,
+            StateScoped(Screen::Playing),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Name::new("Pause Panel"),
+                NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Px(8.),
+                        padding: UiRect::all(Px(16.)),
+                        border: UiRect::all(Px(2.)),
+                        ..default()
+                    },
+                    background_color: GRAY_800.into(),
+                    border_color: GRAY_900.into(),
+                    ..default()
+                },
+            ))
+            .with_children(|panel| {
+                panel.spawn(TextBundle::from_section(
+                    "Paused",
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 30.,
+                        color: WHITE.into(),
+                    },
+                ));
+
+                for (label, action) in [
+                    ("Resume", PauseAction::Resume),
+                    ("Settings", PauseAction::Settings),
+                    ("Save", PauseAction::Save),
+                    ("Quit to Title", PauseAction::QuitToTitle),
+                ] {
+                    panel.button(label, font.clone()).insert(action);
+                }
+            });
+        });
+}
+
+fn handle_pause_action(
+    mut commands: Commands,
+    mut button_query: InteractionQuery<&PauseAction>,
+    q_pause_menu: Query<Entity, With<PauseMenu>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    for (interaction, action) in &mut button_query {
+        if !matches!(interaction, Interaction::Pressed) {
+            continue;
+        }
+
+        match action {
+            PauseAction::Resume => close_pause_menu(&mut commands, &q_pause_menu, &mut time),
+            PauseAction::Settings => commands.trigger(OpenSettings),
+            PauseAction::Save => commands.trigger(SaveRun),
+            // leaving the run cleans it up, see `reset_run`
+            PauseAction::QuitToTitle => next_screen.set(Screen::Title),
+        }
+    }
+}
+
+fn unpause(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
@@ -2,8 +2,11 @@ use bevy::prelude::*;
 use num_bigint::BigUint;
 
 use crate::{
-    game::{assets::SoundtrackKey, audio::soundtrack::PlaySoundtrack, spawn::level::Ring},
-    screen::{playing::{format_scientific, Currency}, Screen},
+    game::{
+        audio::mixer::AudioMixer, number_format::format_number, settings::Settings,
+        spawn::level::Ring,
+    },
+    screen::{playing::Currency, Screen},
 };
 
 use super::widgets::{CurrencyText, CyclesCountText, PendingCurrencyText, ToggleAudio};
@@ -14,34 +17,21 @@ pub(super) fn plugin(app: &mut App) {
         (update_cycles, update_currency).run_if(in_state(Screen::Playing)),
     );
 
-    app.insert_resource::<AudioSettings>(AudioSettings { enabled: true });
     app.observe(toggle_audio);
 }
 
-#[derive(Resource, Default)]
-pub struct AudioSettings {
-    pub enabled: bool,
-}
-
 fn toggle_audio(
     _trigger: Trigger<ToggleAudio>,
-    mut commands: Commands,
-    mut audio_settings: ResMut<AudioSettings>
+    mut mixer: ResMut<AudioMixer>,
 ) {
-    audio_settings.enabled = !audio_settings.enabled;
-
-    commands.trigger(
-        match audio_settings.enabled {
-            true => PlaySoundtrack::Key(SoundtrackKey::Gameplay),
-            false => PlaySoundtrack::Disable
-        }
-    );
+    mixer.muted = !mixer.muted;
 }
 
 fn update_cycles(
     q_rings: Query<&Ring>,
     mut q_text: Query<&mut Text, With<CyclesCountText>>,
     old_count: Local<BigUint>,
+    settings: Res<Settings>,
 ) {
     if q_text.is_empty() {
         return;
@@ -53,8 +43,8 @@ fn update_cycles(
         .iter()
         .fold(BigUint::ZERO, |acc, ring| acc + &ring.cycle_count);
 
-    if total_cycles != *old_count {
-        cycle_count_text.sections[0].value = format!("{}", format_scientific(&total_cycles))
+    if total_cycles != *old_count || settings.is_changed() {
+        cycle_count_text.sections[0].value = format!("{}", format_number(&total_cycles, settings.number_format))
     }
 }
 
@@ -63,6 +53,7 @@ fn update_currency(
     q_rings: Query<&Ring>,
     mut q_currency_text: Query<&mut Text, With<CurrencyText>>,
     mut q_pending_currency_text: Query<&mut Text, (With<PendingCurrencyText>, Without<CurrencyText>)>,
+    settings: Res<Settings>,
 ) {
     let total_pending_amount = q_rings
         .iter()
@@ -74,11 +65,11 @@ fn update_currency(
     
     currency_text.sections[0].value = format!(
         "${} ",
-        format_scientific(&currency.amount),
+        format_number(&currency.amount, settings.number_format),
     );
 
     pending_currency_text.sections[0].value = format!(
         " ${}",
-        format_scientific(&total_pending_amount),
+        format_number(&total_pending_amount, settings.number_format),
     );
 }
@@ -0,0 +1,284 @@
+//! The settings menu, an overlay that can be opened from the title screen or during play.
+
+use bevy::{
+    color::palettes::{
+        css::WHITE,
+        tailwind::{GRAY_800, GRAY_900},
+    },
+    ecs::system::EntityCommands,
+    prelude::*,
+    ui::Val::*,
+};
+
+use crate::{
+    game::{
+        assets::{FontKey, HandleMap},
+        settings::Settings,
+    },
+    screen::Screen,
+};
+
+use super::{
+    interaction::InteractionQuery,
+    widgets::{Slider, Widgets},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.observe(open_settings);
+
+    app.add_systems(
+        Update,
+        (
+            handle_settings_action,
+            apply_volume_sliders,
+            update_settings_button_text,
+        )
+            .run_if(any_with_component::<SettingsMenu>),
+    );
+}
+
+/// Trigger this event to open the settings overlay on top of the current screen.
+#[derive(Event, Clone)]
+pub struct OpenSettings;
+
+#[derive(Component)]
+pub struct SettingsMenu;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum SettingsAction {
+    ToggleFullscreen,
+    ToggleVsync,
+    CycleNumberFormat,
+    CycleScrollingText,
+    ToggleReduceMotion,
+    Back,
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum VolumeSlider {
+    Master,
+    Music,
+    Sfx,
+    Ui,
+}
+
+fn open_settings(
+    _trigger: Trigger<OpenSettings>,
+    mut commands: Commands,
+    q_settings_menu: Query<Entity, With<SettingsMenu>>,
+    screen: Res<State<Screen>>,
+    settings: Res<Settings>,
+    font_handles: Res<HandleMap<FontKey>>,
+) {
+    if !q_settings_menu.is_empty() {
+        return;
+    }
+
+    let font = font_handles[&FontKey::Default].clone();
+
+    commands
+        .spawn((
+            Name::new("Settings Menu"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    height: Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                background_color: Color::srgba(0., 0., 0., 0.6).into(),
+                z_index: ZIndex::Global(100),
+                ..default()
+            },
+            SettingsMenu,
+            StateScoped(screen.get().clone()),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Name::new("Settings Panel"),
+                NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Px(8.),
+                        padding: UiRect::all(Px(16.)),
+                        border: UiRect::all(Px(2.)),
+                        ..default()
+                    },
+                    background_color: GRAY_800.into(),
+                    border_color: GRAY_900.into(),
+                    ..default()
+                },
+            ))
+            .with_children(|panel| {
+                panel.spawn(TextBundle::from_section(
+                    "Settings",
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 30.,
+                        color: WHITE.into(),
+                    },
+                ));
+
+                settings_row(panel, "Master Volume", &font).with_children(|row| {
+                    row.slider(settings.master_volume).insert(VolumeSlider::Master);
+                });
+                settings_row(panel, "Music Volume", &font).with_children(|row| {
+                    row.slider(settings.music_volume).insert(VolumeSlider::Music);
+                });
+                settings_row(panel, "SFX Volume", &font).with_children(|row| {
+                    row.slider(settings.sfx_volume).insert(VolumeSlider::Sfx);
+                });
+                settings_row(panel, "UI Volume", &font).with_children(|row| {
+                    row.slider(settings.ui_volume).insert(VolumeSlider::Ui);
+                });
+
+                for (label, action) in [
+                    ("Fullscreen", SettingsAction::ToggleFullscreen),
+                    ("Vsync", SettingsAction::ToggleVsync),
+                    ("Numbers", SettingsAction::CycleNumberFormat),
+                    ("Floating Text", SettingsAction::CycleScrollingText),
+                    ("Reduce Motion", SettingsAction::ToggleReduceMotion),
+                ] {
+                    settings_row(panel, label, &font).with_children(|row| {
+                        row.button(settings_button_text(action, &settings), font.clone())
+                            .insert(action);
+                    });
+                }
+
+                panel
+                    .button("Back", font.clone())
+                    .insert(SettingsAction::Back);
+            });
+        });
+}
+
+fn settings_row<'a>(
+    panel: &'a mut ChildBuilder,
+    label: &str,
+    font: &Handle<Font>,
+) -> EntityCommands<'a> {
+    let mut row = panel.spawn((
+        Name::new("Settings Row"),
+        NodeBundle {
+            style: Style {
+                width: Px(360.),
+                display: Display::Flex,
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        },
+    ));
+
+    row.with_children(|row| {
+        row.spawn(TextBundle::from_section(
+            label,
+            TextStyle {
+                font: font.clone(),
+                font_size: 18.,
+                color: WHITE.into(),
+            },
+        ));
+    });
+
+    row
+}
+
+fn on_off(value: bool) -> &'static str {
+    if value {
+        "On"
+    } else {
+        "Off"
+    }
+}
+
+fn settings_button_text(action: SettingsAction, settings: &Settings) -> String {
+    match action {
+        SettingsAction::ToggleFullscreen => on_off(settings.fullscreen).to_string(),
+        SettingsAction::ToggleVsync => on_off(settings.vsync).to_string(),
+        SettingsAction::CycleNumberFormat => settings.number_format.as_str().to_string(),
+        SettingsAction::CycleScrollingText => settings.scrolling_text.as_str().to_string(),
+        SettingsAction::ToggleReduceMotion => on_off(settings.reduce_motion).to_string(),
+        SettingsAction::Back => String::from("Back"),
+    }
+}
+
+fn handle_settings_action(
+    mut commands: Commands,
+    mut settings: ResMut<Settings>,
+    mut button_query: InteractionQuery<&SettingsAction>,
+    q_settings_menu: Query<Entity, With<SettingsMenu>>,
+) {
+    for (interaction, action) in &mut button_query {
+        if !matches!(interaction, Interaction::Pressed) {
+            continue;
+        }
+
+        match action {
+            SettingsAction::ToggleFullscreen => settings.fullscreen = !settings.fullscreen,
+            SettingsAction::ToggleVsync => settings.vsync = !settings.vsync,
+            SettingsAction::CycleNumberFormat => {
+                settings.number_format = settings.number_format.next()
+            }
+            SettingsAction::CycleScrollingText => {
+                settings.scrolling_text = settings.scrolling_text.next()
+            }
+            SettingsAction::ToggleReduceMotion => {
+                settings.reduce_motion = !settings.reduce_motion
+            }
+            SettingsAction::Back => {
+                for menu in &q_settings_menu {
+                    commands.entity(menu).despawn_recursive();
+                }
+            }
+        }
+    }
+}
+
+fn apply_volume_sliders(
+    mut settings: ResMut<Settings>,
+    q_slider: Query<(&Slider, &VolumeSlider), Changed<Slider>>,
+) {
+    for (slider, volume_slider) in &q_slider {
+        let current_volume = match volume_slider {
+            VolumeSlider::Master => settings.master_volume,
+            VolumeSlider::Music => settings.music_volume,
+            VolumeSlider::Sfx => settings.sfx_volume,
+            VolumeSlider::Ui => settings.ui_volume,
+        };
+
+        if current_volume == slider.value {
+            continue;
+        }
+
+        match volume_slider {
+            VolumeSlider::Master => settings.master_volume = slider.value,
+            VolumeSlider::Music => settings.music_volume = slider.value,
+            VolumeSlider::Sfx => settings.sfx_volume = slider.value,
+            VolumeSlider::Ui => settings.ui_volume = slider.value,
+        }
+    }
+}
+
+fn update_settings_button_text(
+    settings: Res<Settings>,
+    q_button: Query<(&SettingsAction, &Children)>,
+    mut q_text: Query<&mut Text>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    for (action, children) in &q_button {
+        for child in children {
+            if let Ok(mut text) = q_text.get_mut(*child) {
+                text.sections[0].value = settings_button_text(*action, &settings);
+            }
+        }
+    }
+}
@@ -1,10 +1,16 @@
 use std::{str::FromStr, time::Duration};
 
 use bevy::{
-    audio::PlaybackMode, color::palettes::{
+    audio::PlaybackMode,
+    color::palettes::{
         css::BLACK,
         tailwind::{GRAY_400, GRAY_500, GRAY_600, GRAY_700, GRAY_800, GRAY_900},
-    }, math::VectorSpace, prelude::*, utils::HashSet
+    },
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    math::VectorSpace,
+    prelude::*,
+    ui::Val::Px,
+    utils::{HashMap, HashSet},
 };
 use bevy_mod_picking::{
     events::{Click, Pointer},
@@ -12,32 +18,59 @@ use bevy_mod_picking::{
 };
 use bevy_tweening::{lens::{TransformPositionLens, TransformScaleLens}, Animator, EaseFunction, Tween};
 use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     game::{
-        assets::{FontKey, HandleMap, SfxKey}, audio::soundtrack::PlaySfx, camera::CAMERA_DISABLE_TWEEN_NUMBER, materials::materials::{RingMaterial, SocketMaterial, SocketUiMaterial}, spawn::level::{
+        assets::{FontKey, HandleMap, SfxKey},
+        audio::{mixer::AudioBus, soundtrack::PlaySfx},
+        camera::CAMERA_DISABLE_TWEEN_NUMBER,
+        materials::materials::{RingMaterial, SocketMaterial, SocketUiMaterial},
+        multiplier::Multiplier,
+        number_format::format_number,
+        settings::Settings,
+        spawn::level::{
             map_socket_color, map_socket_color_hotkey, map_socket_highlight_color, socket_position,
             spawn_ring, spawn_socket, GameplayMeshes, Ring, RingIndex, Socket, SocketColor,
-            RING_RADIUS, RING_THICKNESS,
-        }
+            NEW_RING_SOCKETS, RING_RADIUS, RING_THICKNESS,
+        },
     },
     screen::{playing::Currency, Screen},
     ui::widgets::Widgets,
 };
 
 use super::{
+    event_log::LogEvent,
     interaction::InteractionPalette,
-    widgets::{Hotbar, ShopButton, UpgradeShop},
+    toast::{ShowToast, ToastPriority},
+    widgets::{Hotbar, ShopButton, ShopButtonPriceText, UpgradeShop},
 };
 
+const SHOP_SCROLL_PIXELS_PER_LINE: f32 = 20.;
+
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         Update,
-        (fade_stuff_you_cant_afford).run_if(in_state(Screen::Playing)),
+        (fade_stuff_you_cant_afford, format_shop_prices, announce_affordable_upgrades)
+            .run_if(in_state(Screen::Playing)),
+    );
+
+    app.add_systems(
+        Update,
+        (
+            (arrange_shop_sections, label_shop_controls),
+            scroll_shop,
+        )
+            .chain()
+            .run_if(any_with_component::<UpgradeShop>),
     );
 
     app.observe(on_new_shop);
     app.observe(on_purchase);
+    app.observe(cycle_shop_sort);
+    app.observe(toggle_hide_unaffordable);
+
+    app.init_resource::<ShopView>();
 
     app.init_resource::<Unlocks>();
     app.init_resource::<UpgradeHistory>();
@@ -48,7 +81,7 @@ pub struct UpgradeHistory {
     pub history: HashSet<UpgradeKind>,
 }
 
-#[derive(Default, PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(Default, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub enum UpgradeKind {
     #[default]
     None,
@@ -61,26 +94,36 @@ pub enum UpgradeKind {
 
 #[derive(Default, PartialEq, Eq, Hash, Clone)]
 pub struct Upgrade {
-    upgrade_kind: UpgradeKind,
-    cost: BigUint,
+    pub upgrade_kind: UpgradeKind,
+    pub(super) cost: BigUint,
 }
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+impl Upgrade {
+    /// The upgrade, given away for nothing.
+    pub fn free(upgrade_kind: UpgradeKind) -> Self {
+        Self {
+            upgrade_kind,
+            cost: BigUint::ZERO,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub struct AddSocketUpgrade {
-    level: u32,
+    pub level: u32,
 }
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub struct AddRingUpgrade {
     pub level: u32,
 }
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub struct AddColorUpgrade {
-    color: SocketColor,
+    pub color: SocketColor,
 }
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub struct EnhanceColorUpgrade {
     pub color: SocketColor,
     pub tier: u32,
@@ -97,7 +140,7 @@ pub struct Purchase {
     pub upgrade_button_entity: Entity,
 }
 
-fn upgrade_cost(upgrade_kind: UpgradeKind) -> BigUint {
+pub(super) fn upgrade_cost(upgrade_kind: UpgradeKind) -> BigUint {
     match upgrade_kind {
         UpgradeKind::None => BigUint::ZERO,
         UpgradeKind::AddSocket(upgrade) => {
@@ -105,22 +148,25 @@ fn upgrade_cost(upgrade_kind: UpgradeKind) -> BigUint {
 
             if upgrade.level <= 10 {
                 let scale_factor_per_level = 0.115;
-                multiply_biguint_with_float(
-                    &base_add_socket_cost,
-                    (upgrade.level as f32).powf(1. + scale_factor_per_level * upgrade.level as f32),
+                Multiplier::from_f64(
+                    (upgrade.level as f32).powf(1. + scale_factor_per_level * upgrade.level as f32)
+                        as f64,
                 )
+                .apply(&base_add_socket_cost)
             } else {
                 let scale_factor_per_level = 0.125;
-                let cost_at_scaling_threshold = multiply_biguint_with_float(
-                    &base_add_socket_cost,
-                    (upgrade.level as f32).powf(1. + scale_factor_per_level * 10. as f32),
-                );
+                let cost_at_scaling_threshold = Multiplier::from_f64(
+                    (upgrade.level as f32).powf(1. + scale_factor_per_level * 10. as f32) as f64,
+                )
+                .apply(&base_add_socket_cost);
 
                 let scaling_factor_after_threshold = 0.07;
-                let cost_after_threshold = multiply_biguint_with_float(
-                    &base_add_socket_cost,
-                    (upgrade.level as f32).powf(1. + scaling_factor_after_threshold * upgrade.level as f32),
-                );
+                let cost_after_threshold = Multiplier::from_f64(
+                    (upgrade.level as f32)
+                        .powf(1. + scaling_factor_after_threshold * upgrade.level as f32)
+                        as f64,
+                )
+                .apply(&base_add_socket_cost);
 
                 cost_at_scaling_threshold + cost_after_threshold
             }
@@ -172,15 +218,112 @@ fn upgrade_cost(upgrade_kind: UpgradeKind) -> BigUint {
     }
 }
 
-pub fn multiply_biguint_with_float(bigint: &BigUint, float: f32) -> BigUint {
-    let scale = 1_000_000u32;
-    let scaled_float = (float * scale as f32).round() as u64;
-    let scaled_product = bigint * scaled_float;
-    scaled_product / BigUint::from(scale)
+/// The scrolling list holding every [`ShopSection`].
+#[derive(Component, Default)]
+pub struct UpgradeButtonsContainer {
+    offset: f32,
+}
+
+/// Hovering this lets the mouse wheel scroll the shop instead of zooming.
+#[derive(Component)]
+pub struct ShopScrollArea;
+
+/// The shop's headings, in the order they're shown.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ShopSection {
+    Sockets,
+    Rings,
+    Colors,
+    Enhancements,
+    Endgame,
+}
+
+impl ShopSection {
+    pub const ALL: [ShopSection; 5] = [
+        ShopSection::Sockets,
+        ShopSection::Rings,
+        ShopSection::Colors,
+        ShopSection::Enhancements,
+        ShopSection::Endgame,
+    ];
+
+    pub fn of(upgrade_kind: UpgradeKind) -> Self {
+        match upgrade_kind {
+            UpgradeKind::AddSocket(_) => ShopSection::Sockets,
+            UpgradeKind::AddRing(_) => ShopSection::Rings,
+            UpgradeKind::AddColor(_) => ShopSection::Colors,
+            UpgradeKind::EnhanceColor(_) => ShopSection::Enhancements,
+            UpgradeKind::None | UpgradeKind::Win => ShopSection::Endgame,
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            ShopSection::Sockets => "Sockets",
+            ShopSection::Rings => "Rings",
+            ShopSection::Colors => "Colors",
+            ShopSection::Enhancements => "Enhancements",
+            ShopSection::Endgame => "Endgame",
+        }
+    }
 }
 
+/// A section's heading and buttons, hidden while there's nothing to show in it.
+#[derive(Component)]
+pub struct ShopSectionNode(pub ShopSection);
+
+/// Where a section's [`ShopButton`]s are spawned.
 #[derive(Component)]
-pub struct UpgradeButtonsContainer;
+pub struct ShopSectionButtons(pub ShopSection);
+
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum ShopSort {
+    #[default]
+    UnlockOrder,
+    Price,
+    /// What can be bought right now first, cheapest first within each.
+    Affordability,
+}
+
+impl ShopSort {
+    fn next(self) -> Self {
+        match self {
+            ShopSort::UnlockOrder => ShopSort::Price,
+            ShopSort::Price => ShopSort::Affordability,
+            ShopSort::Affordability => ShopSort::UnlockOrder,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            ShopSort::UnlockOrder => "unlock order",
+            ShopSort::Price => "price",
+            ShopSort::Affordability => "affordable",
+        }
+    }
+}
+
+/// How the player wants the shop sorted and filtered.
+#[derive(Resource, Default)]
+pub struct ShopView {
+    pub sort: ShopSort,
+    pub hide_unaffordable: bool,
+}
+
+/// Trigger this event to switch the shop to the next [`ShopSort`].
+#[derive(Event)]
+pub struct CycleShopSort;
+
+/// Trigger this event to show or hide upgrades the player can't afford yet.
+#[derive(Event)]
+pub struct ToggleHideUnaffordable;
+
+/// Which setting a shop control button changes, so its label can be kept up to date.
+#[derive(Component)]
+pub enum ShopControl {
+    Sort,
+    HideUnaffordable,
+}
 
 fn on_new_shop(
     trigger: Trigger<NewShop>,
@@ -188,10 +331,11 @@ fn on_new_shop(
     mut unlocks: ResMut<Unlocks>,
     font_handles: ResMut<HandleMap<FontKey>>,
 ) {
-    unlocks.0 = add_ring_and_socket_unlocks();
-    unlocks.0.extend(build_color_unlocks());
-    unlocks.0.extend(build_color_enhance_unlocks());
-    unlocks.0.push(Unlock {
+    *unlocks = Unlocks::default();
+    unlocks.pending = add_ring_and_socket_unlocks();
+    unlocks.pending.extend(build_color_unlocks());
+    unlocks.pending.extend(build_color_enhance_unlocks());
+    unlocks.pending.push(Unlock {
         when: vec![UpgradeKind::EnhanceColor(EnhanceColorUpgrade {
             color: SocketColor::GREEN,
             tier: 1,
@@ -389,12 +533,43 @@ fn build_color_enhance_unlocks() -> Vec<Unlock> {
     ]
 }
 
+/// Upgrades that haven't made it into the shop yet, and what has to be bought first.
+/// Rebuilt for every run's shop.
 #[derive(Default, Resource)]
-struct Unlocks(Vec<Unlock>);
+pub(super) struct Unlocks {
+    pub pending: Vec<Unlock>,
+    /// The [`ShopItem::unlock_order`] the next upgrade to reach the shop gets.
+    pub unlock_order: usize,
+}
+
+pub(super) struct Unlock {
+    pub when: Vec<UpgradeKind>,
+    pub then: UpgradeKind,
+}
 
-struct Unlock {
-    when: Vec<UpgradeKind>,
-    then: UpgradeKind,
+impl Unlocks {
+    /// What buying `upgrade_kind` on top of `history` would put in the shop.
+    pub fn revealed_by(&self, upgrade_kind: UpgradeKind, history: &UpgradeHistory) -> Vec<UpgradeKind> {
+        self.pending
+            .iter()
+            .filter(|unlock| {
+                unlock.when.contains(&upgrade_kind)
+                    && unlock
+                        .when
+                        .iter()
+                        .all(|item| *item == upgrade_kind || history.history.contains(item))
+            })
+            .map(|unlock| unlock.then)
+            .collect()
+    }
+}
+
+/// Which upgrade a [`ShopButton`] sells.
+#[derive(Component)]
+pub struct ShopItem {
+    pub upgrade_kind: UpgradeKind,
+    /// Counts up as upgrades appear in the shop, for sorting them in the order they were unlocked.
+    pub unlock_order: usize,
 }
 
 fn on_purchase(
@@ -413,11 +588,11 @@ fn on_purchase(
     mut socket_ui_materials: ResMut<Assets<SocketUiMaterial>>,
     mut unlocks: ResMut<Unlocks>,
     q_camera: Query<(Entity, &Transform), (With<Camera>, Without<Socket>)>,
-    q_upgrade_button_container: Query<Entity, With<UpgradeButtonsContainer>>,
+    q_section_buttons: Query<(Entity, &ShopSectionButtons)>,
     gameplay_meshes: Res<GameplayMeshes>,
     font_handles: ResMut<HandleMap<FontKey>>,
     time: Res<Time>,
-    sfx_handles: Res<HandleMap<SfxKey>>,
+    settings: Res<Settings>,
 ) {
     let purchase = trigger.event();
     let (mut socket_materials, mut ring_materials) = materials;
@@ -437,14 +612,23 @@ fn on_purchase(
             .history
             .insert(purchase.upgrade.upgrade_kind.clone());
 
-        commands
-            .entity(purchase.upgrade_button_entity)
-            .despawn_recursive();
+        commands.trigger(LogEvent {
+            text: format!("Bought: {}", upgrade_description(&purchase.upgrade).into()),
+            ring: None,
+        });
+
+        // upgrades granted from outside the shop may not have a button
+        if let Some(button) = commands.get_entity(purchase.upgrade_button_entity) {
+            button.despawn_recursive();
+        }
     }
 
     commands.trigger(PlaySfx {
         key: SfxKey::Unlock,
-        volume: 2.
+        bus: AudioBus::Ui,
+        volume: 2.,
+        speed: 1.,
+        position: None,
     });
 
     let ring_count = q_rings.iter().count();
@@ -474,7 +658,6 @@ fn on_purchase(
                     highlight_color: map_socket_highlight_color(SocketColor::NONE),
                     bevel_color: { BLACK.into() },
                     data: Vec4::new(-1000., socket_trigger_duration, 0., 0.),
-                    data2: Vec4::ZERO,
                 });
 
                 commands
@@ -521,7 +704,14 @@ fn on_purchase(
 
             let (camera_entity, camera_transform)= q_camera.single();
 
-            if existing_ring_count == 1 {
+            if existing_ring_count == 1 && settings.reduce_motion {
+                commands
+                    .entity(camera_entity)
+                    .insert(Transform {
+                        scale: camera_transform.scale * 2.,
+                        ..*camera_transform
+                    });
+            } else if existing_ring_count == 1 {
                 let tween = Tween::new(
                     EaseFunction::QuadraticIn,
                     Duration::from_secs(2),
@@ -536,15 +726,15 @@ fn on_purchase(
 
             spawn_ring(
                 &mut commands,
-                ring_index,
+                &mut ring_index,
                 gameplay_meshes.quad512.clone(),
                 gameplay_meshes.quad64.clone(),
                 ring_materials.add(RingMaterial {
                     data: Vec4::new(RING_RADIUS, RING_THICKNESS, 0., 0.),
                 }),
-                socket_materials,
-                2,
-                time,
+                &mut socket_materials,
+                NEW_RING_SOCKETS.to_vec(),
+                &time,
                 existing_ring_count,
             )
         }
@@ -553,16 +743,34 @@ fn on_purchase(
     }
 
     // 2. Unlock what is available now
-    let container = q_upgrade_button_container.single();
 
     let mut indices_to_remove = Vec::new();
+    let mut new_in_shop: Vec<String> = Vec::new();
+
+    let Unlocks {
+        pending,
+        unlock_order,
+    } = &mut *unlocks;
 
-    for (index, unlock) in unlocks.0.iter().enumerate() {
+    for (index, unlock) in pending.iter().enumerate() {
         if unlock
             .when
             .iter()
             .all(|item| upgrade_history.history.contains(item))
         {
+            // a loaded run has already bought some of what it unlocks
+            if upgrade_history.history.contains(&unlock.then) {
+                indices_to_remove.push(index);
+                continue;
+            }
+
+            let Some((container, _)) = q_section_buttons
+                .iter()
+                .find(|(_, section)| section.0 == ShopSection::of(unlock.then))
+            else {
+                continue;
+            };
+
             commands
                 .entity(container)
                 .with_children(|button_container| {
@@ -571,6 +779,8 @@ fn on_purchase(
                         cost: upgrade_cost(unlock.then),
                     };
 
+                    new_in_shop.push(upgrade_description(&new_upgrade).into());
+
                     let mut button_entity_commands = button_container.shop_button(
                         &new_upgrade.cost,
                         upgrade_description(&new_upgrade),
@@ -578,6 +788,12 @@ fn on_purchase(
                     );
                     let button_entity = button_entity_commands.id();
 
+                    button_entity_commands.insert(ShopItem {
+                        upgrade_kind: new_upgrade.upgrade_kind,
+                        unlock_order: *unlock_order,
+                    });
+                    *unlock_order += 1;
+
                     button_entity_commands.insert(On::<Pointer<Click>>::commands_mut(
                         move |_ev, commands| {
                             commands.trigger(Purchase {
@@ -593,7 +809,200 @@ fn on_purchase(
     }
 
     for index in indices_to_remove.iter().rev() {
-        unlocks.0.remove(*index);
+        pending.remove(*index);
+    }
+
+    // the starting stock isn't news
+    if purchase.upgrade.upgrade_kind != UpgradeKind::None {
+        for description in new_in_shop {
+            commands.trigger(ShowToast {
+                title: String::from("New in the shop"),
+                body: description,
+                ..default()
+            });
+        }
+    }
+}
+
+/// Let the player know when an upgrade they couldn't afford becomes affordable.
+fn announce_affordable_upgrades(
+    mut commands: Commands,
+    currency: Res<Currency>,
+    q_shop_button: Query<(Entity, &ShopButton)>,
+    mut affordable: Local<HashMap<Entity, bool>>,
+) {
+    for (entity, button) in &q_shop_button {
+        let can_afford = button.price <= currency.amount;
+
+        // buttons that just showed up were already announced as new
+        if affordable.insert(entity, can_afford) == Some(false) && can_afford {
+            commands.trigger(ShowToast {
+                title: String::from("Affordable"),
+                body: button.description.clone(),
+                priority: ToastPriority::Low,
+                ..default()
+            });
+        }
+    }
+
+    affordable.retain(|entity, _| q_shop_button.contains(*entity));
+}
+
+fn cycle_shop_sort(_trigger: Trigger<CycleShopSort>, mut shop_view: ResMut<ShopView>) {
+    shop_view.sort = shop_view.sort.next();
+}
+
+fn toggle_hide_unaffordable(
+    _trigger: Trigger<ToggleHideUnaffordable>,
+    mut shop_view: ResMut<ShopView>,
+) {
+    shop_view.hide_unaffordable = !shop_view.hide_unaffordable;
+}
+
+/// Sorts each section's buttons, hides the ones filtered out and any section left empty.
+/// Affordability changes with every payout, so this runs every frame.
+fn arrange_shop_sections(
+    mut commands: Commands,
+    currency: Res<Currency>,
+    shop_view: Res<ShopView>,
+    q_section_buttons: Query<(Entity, &ShopSectionButtons, Option<&Children>)>,
+    mut q_section_node: Query<(&ShopSectionNode, &mut Style), Without<ShopButton>>,
+    mut q_shop_button: Query<(&ShopButton, &ShopItem, &mut Style)>,
+) {
+    let mut visible_sections = Vec::new();
+
+    for (section_entity, section, children) in &q_section_buttons {
+        let Some(children) = children else {
+            continue;
+        };
+
+        let mut buttons: Vec<(Entity, bool, &ShopButton, &ShopItem)> = children
+            .iter()
+            .filter_map(|child| {
+                let (button, item, _) = q_shop_button.get(*child).ok()?;
+                Some((*child, button.price <= currency.amount, button, item))
+            })
+            .collect();
+
+        match shop_view.sort {
+            ShopSort::UnlockOrder => buttons.sort_by_key(|(_, _, _, item)| item.unlock_order),
+            ShopSort::Price => buttons.sort_by(|(_, _, a, a_item), (_, _, b, b_item)| {
+                a.price
+                    .cmp(&b.price)
+                    .then(a_item.unlock_order.cmp(&b_item.unlock_order))
+            }),
+            ShopSort::Affordability => {
+                buttons.sort_by(|(_, a_affordable, a, a_item), (_, b_affordable, b, b_item)| {
+                    b_affordable
+                        .cmp(a_affordable)
+                        .then(a.price.cmp(&b.price))
+                        .then(a_item.unlock_order.cmp(&b_item.unlock_order))
+                })
+            }
+        }
+
+        let order: Vec<(Entity, bool)> = buttons
+            .iter()
+            .map(|(entity, affordable, _, _)| (*entity, *affordable))
+            .collect();
+
+        if !order.iter().map(|(entity, _)| entity).eq(children.iter()) {
+            let entities: Vec<Entity> = order.iter().map(|(entity, _)| *entity).collect();
+            commands.entity(section_entity).replace_children(&entities);
+        }
+
+        let mut any_visible = false;
+        for (entity, affordable) in order {
+            let visible = affordable || !shop_view.hide_unaffordable;
+            any_visible |= visible;
+
+            let display = if visible { Display::Flex } else { Display::None };
+            if let Ok((_, _, mut style)) = q_shop_button.get_mut(entity) {
+                if style.display != display {
+                    style.display = display;
+                }
+            }
+        }
+
+        if any_visible {
+            visible_sections.push(section.0);
+        }
+    }
+
+    for (section, mut style) in &mut q_section_node {
+        let display = if visible_sections.contains(&section.0) {
+            Display::Flex
+        } else {
+            Display::None
+        };
+
+        if style.display != display {
+            style.display = display;
+        }
+    }
+}
+
+fn label_shop_controls(
+    shop_view: Res<ShopView>,
+    q_control: Query<(Ref<ShopControl>, &Children)>,
+    mut q_text: Query<&mut Text>,
+) {
+    for (control, children) in &q_control {
+        if !shop_view.is_changed() && !control.is_added() {
+            continue;
+        }
+
+        let label = match *control {
+            ShopControl::Sort => format!("Sort: {}", shop_view.sort.as_str()),
+            ShopControl::HideUnaffordable => format!(
+                "Unaffordable: {}",
+                if shop_view.hide_unaffordable { "hidden" } else { "shown" }
+            ),
+        };
+
+        for child in children {
+            if let Ok(mut text) = q_text.get_mut(*child) {
+                text.sections[0].value.clone_from(&label);
+            }
+        }
+    }
+}
+
+/// Works like the event log: the list is moved within the scroll area while it's hovered.
+fn scroll_shop(
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    q_scroll_area: Query<(&Interaction, &Node), With<ShopScrollArea>>,
+    mut q_list: Query<(&mut UpgradeButtonsContainer, &mut Style, &Node)>,
+) {
+    let Ok((interaction, scroll_area_node)) = q_scroll_area.get_single() else {
+        return;
+    };
+
+    let Ok((mut list, mut style, list_node)) = q_list.get_single_mut() else {
+        return;
+    };
+
+    let hovered = *interaction != Interaction::None;
+    let max_scroll = (list_node.size().y - scroll_area_node.size().y).max(0.);
+
+    let mut offset = list.offset;
+    for event in mouse_wheel_events.read() {
+        if !hovered {
+            continue;
+        }
+
+        offset += match event.unit {
+            MouseScrollUnit::Line => event.y * SHOP_SCROLL_PIXELS_PER_LINE,
+            MouseScrollUnit::Pixel => event.y,
+        };
+    }
+
+    // the list shrinks as things are bought, which can leave it scrolled past the end
+    offset = offset.clamp(-max_scroll, 0.);
+
+    if offset != list.offset {
+        list.offset = offset;
+        style.top = Px(offset);
     }
 }
 
@@ -629,7 +1038,18 @@ fn fade_stuff_you_cant_afford(
     }
 }
 
-fn upgrade_description(upgrade: &Upgrade) -> impl Into<String> {
+fn format_shop_prices(
+    settings: Res<Settings>,
+    mut q_price_text: Query<(Ref<ShopButtonPriceText>, &mut Text)>,
+) {
+    for (price_text, mut text) in q_price_text.iter_mut() {
+        if settings.is_changed() || price_text.is_added() {
+            text.sections[0].value = format!("${}", format_number(&price_text.price, settings.number_format));
+        }
+    }
+}
+
+pub(super) fn upgrade_description(upgrade: &Upgrade) -> impl Into<String> {
     let description = match upgrade.upgrade_kind {
         UpgradeKind::None => "Errmm.. This shouldn't be for sale",
         UpgradeKind::AddSocket(_) => "Add a socket",
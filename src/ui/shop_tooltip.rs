@@ -0,0 +1,330 @@
+//! A tooltip beside whichever shop button is hovered, spelling out what the upgrade actually does.
+
+use bevy::{
+    color::palettes::{
+        css::{ORANGE, WHITE},
+        tailwind::{GRAY_400, GRAY_800, GRAY_900, GREEN_400, RED_400},
+    },
+    prelude::*,
+    ui::Val::*,
+};
+use bevy_mod_picking::picking_core::Pickable;
+use num_bigint::BigUint;
+
+use crate::{
+    game::{
+        achievements::Achievements,
+        assets::{FontKey, HandleMap},
+        income_estimate::{estimate_income_per_cycle, RingLayout},
+        number_format::format_number,
+        settings::Settings,
+        spawn::level::{Ring, Socket, SocketColor, NEW_RING_SOCKETS, RING_CYCLE_DURATION},
+    },
+    screen::Screen,
+};
+
+use super::{
+    hotbar::map_socket_color_description_text,
+    shop::{
+        upgrade_cost, upgrade_description, AddSocketUpgrade, ShopItem, Unlocks, Upgrade,
+        UpgradeHistory, UpgradeKind,
+    },
+    widgets::ShopButton,
+};
+
+const TOOLTIP_WIDTH: f32 = 260.;
+/// Room for the shop panel to the tooltip's right.
+const TOOLTIP_RIGHT: f32 = 266.;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (toggle_shop_tooltip, position_shop_tooltip)
+            .chain()
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+#[derive(Component)]
+struct ShopTooltip {
+    button: Entity,
+}
+
+fn toggle_shop_tooltip(
+    mut commands: Commands,
+    q_button: Query<(Entity, &Interaction, &ShopItem, &ShopButton), Changed<Interaction>>,
+    q_tooltip: Query<(Entity, &ShopTooltip)>,
+    q_ring: Query<&Ring>,
+    q_socket: Query<&Socket>,
+    upgrade_history: Res<UpgradeHistory>,
+    unlocks: Res<Unlocks>,
+    achievements: Res<Achievements>,
+    settings: Res<Settings>,
+    font_handles: Res<HandleMap<FontKey>>,
+) {
+    for (button_entity, interaction, item, button) in &q_button {
+        let existing = q_tooltip
+            .iter()
+            .find(|(_, tooltip)| tooltip.button == button_entity);
+
+        match (interaction, existing) {
+            (Interaction::None, Some((tooltip_entity, _))) => {
+                commands.entity(tooltip_entity).despawn_recursive();
+            }
+            (Interaction::Hovered | Interaction::Pressed, None) => {
+                for (tooltip_entity, _) in &q_tooltip {
+                    commands.entity(tooltip_entity).despawn_recursive();
+                }
+
+                let mut rings: Vec<&Ring> = q_ring.iter().collect();
+                rings.sort_by_key(|ring| ring.index);
+                let layouts: Vec<RingLayout> = rings
+                    .iter()
+                    .map(|ring| RingLayout {
+                        sockets: ring
+                            .sockets
+                            .iter()
+                            .map(|socket| {
+                                q_socket
+                                    .get(*socket)
+                                    .map_or(SocketColor::NONE, |socket| socket.color)
+                            })
+                            .collect(),
+                        cycle_duration: ring.cycle_duration,
+                    })
+                    .collect();
+
+                let lines = tooltip_lines(
+                    item.upgrade_kind,
+                    &button.price,
+                    &layouts,
+                    &upgrade_history,
+                    &unlocks,
+                    &achievements,
+                    &settings,
+                );
+
+                spawn_shop_tooltip(
+                    &mut commands,
+                    button_entity,
+                    lines,
+                    font_handles[&FontKey::Default].clone(),
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+fn tooltip_lines(
+    upgrade_kind: UpgradeKind,
+    price: &BigUint,
+    layouts: &[RingLayout],
+    upgrade_history: &UpgradeHistory,
+    unlocks: &Unlocks,
+    achievements: &Achievements,
+    settings: &Settings,
+) -> Vec<(String, Srgba)> {
+    let money = |amount: &BigUint| format!("${}", format_number(amount, settings.number_format));
+    let describe = |upgrade_kind: UpgradeKind| -> String {
+        upgrade_description(&Upgrade {
+            upgrade_kind,
+            cost: upgrade_cost(upgrade_kind),
+        })
+        .into()
+    };
+
+    let mut lines = vec![(describe(upgrade_kind), ORANGE)];
+
+    let mut history_after = upgrade_history.clone();
+    history_after.history.insert(upgrade_kind);
+
+    // the layouts the estimate runs on once it's bought, with any new sockets holding a BLUE orb
+    let mut layouts_after = layouts.to_vec();
+
+    match upgrade_kind {
+        UpgradeKind::EnhanceColor(upgrade) => {
+            lines.push((
+                format!(
+                    "Before: {}",
+                    map_socket_color_description_text(upgrade.color, upgrade_history)
+                ),
+                GRAY_400,
+            ));
+            lines.push((
+                format!(
+                    "After: {}",
+                    map_socket_color_description_text(upgrade.color, &history_after)
+                ),
+                WHITE,
+            ));
+        }
+        UpgradeKind::AddSocket(upgrade) => {
+            let next_price = upgrade_cost(UpgradeKind::AddSocket(AddSocketUpgrade {
+                level: upgrade.level + 1,
+            }));
+
+            lines.push((
+                String::from("Adds an empty socket to the newest ring."),
+                WHITE,
+            ));
+            lines.push((
+                format!(
+                    "Socket #{} costs {}. The next one will cost {} (+{}).",
+                    upgrade.level,
+                    money(price),
+                    money(&next_price),
+                    money(&(&next_price - price.min(&next_price))),
+                ),
+                GRAY_400,
+            ));
+
+            if let Some(newest) = layouts_after.last_mut() {
+                newest.sockets.push(SocketColor::BLUE);
+            }
+        }
+        UpgradeKind::AddColor(upgrade) => {
+            lines.push((
+                map_socket_color_description_text(upgrade.color, upgrade_history),
+                WHITE,
+            ));
+        }
+        UpgradeKind::AddRing(_) => {
+            lines.push((
+                String::from("Adds a new ring with a BLUE orb and an empty socket."),
+                WHITE,
+            ));
+
+            layouts_after.push(RingLayout {
+                sockets: vec![SocketColor::BLUE; NEW_RING_SOCKETS.len()],
+                cycle_duration: RING_CYCLE_DURATION,
+            });
+        }
+        UpgradeKind::None | UpgradeKind::Win => {}
+    }
+
+    let revealed = unlocks.revealed_by(upgrade_kind, upgrade_history);
+    if !revealed.is_empty() {
+        let revealed: Vec<String> = revealed.into_iter().map(describe).collect();
+        lines.push((format!("Reveals: {}", revealed.join(", ")), WHITE));
+    }
+
+    // where the orbs go is up to the player, so there's nothing to estimate for a new color
+    if matches!(
+        upgrade_kind,
+        UpgradeKind::EnhanceColor(_) | UpgradeKind::AddSocket(_) | UpgradeKind::AddRing(_)
+    ) {
+        let payout_multiplier = achievements.payout_multiplier();
+        let before = estimate_income_per_cycle(
+            layouts,
+            |kind| upgrade_history.history.contains(&kind),
+            &payout_multiplier,
+        );
+        let after = estimate_income_per_cycle(
+            &layouts_after,
+            |kind| history_after.history.contains(&kind),
+            &payout_multiplier,
+        );
+
+        let (change, color) = if after >= before {
+            (format!("+{}", money(&(&after - &before))), GREEN_400)
+        } else {
+            (format!("-{}", money(&(&before - &after))), RED_400)
+        };
+
+        lines.push((
+            format!(
+                "Estimated income: {} → {} per cycle",
+                money(&before),
+                money(&after)
+            ),
+            WHITE,
+        ));
+        lines.push((change, color));
+
+        if !matches!(upgrade_kind, UpgradeKind::EnhanceColor(_)) {
+            lines.push((
+                String::from("(assuming each new socket gets a BLUE orb)"),
+                GRAY_400,
+            ));
+        }
+    }
+
+    lines
+}
+
+fn spawn_shop_tooltip(
+    commands: &mut Commands,
+    button: Entity,
+    lines: Vec<(String, Srgba)>,
+    font: Handle<Font>,
+) {
+    commands
+        .spawn((
+            Name::new("Shop Tooltip"),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    right: Px(TOOLTIP_RIGHT),
+                    width: Px(TOOLTIP_WIDTH),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Px(4.),
+                    padding: UiRect::all(Px(8.)),
+                    border: UiRect::all(Px(2.)),
+                    ..default()
+                },
+                background_color: GRAY_800.with_alpha(0.95).into(),
+                border_color: GRAY_900.into(),
+                z_index: ZIndex::Global(60),
+                // hidden until it's been placed next to its button
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            ShopTooltip { button },
+            Pickable::IGNORE,
+            StateScoped(Screen::Playing),
+        ))
+        .with_children(|tooltip| {
+            for (text, color) in lines {
+                tooltip.spawn(TextBundle::from_section(
+                    text,
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 14.,
+                        color: color.into(),
+                    },
+                ));
+            }
+        });
+}
+
+/// Keeps the tooltip level with its button, and gets rid of it once the button's gone.
+fn position_shop_tooltip(
+    mut commands: Commands,
+    mut q_tooltip: Query<(Entity, &ShopTooltip, &Node, &mut Style, &mut Visibility)>,
+    q_button: Query<(&Node, &GlobalTransform), With<ShopButton>>,
+    q_window: Query<&Window>,
+) {
+    let window_height = q_window
+        .get_single()
+        .map_or(f32::INFINITY, |window| window.height());
+
+    for (entity, tooltip, node, mut style, mut visibility) in &mut q_tooltip {
+        let Ok((button_node, button_transform)) = q_button.get(tooltip.button) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+
+        let button_top = button_transform.translation().y - button_node.size().y / 2.;
+        let top = button_top.min(window_height - node.size().y - 8.).max(8.);
+
+        if style.top != Px(top) {
+            style.top = Px(top);
+        }
+
+        // the first frame it has no size yet, so it would be placed wrong
+        if node.size().y > 0. && *visibility == Visibility::Hidden {
+            *visibility = Visibility::Inherited;
+        }
+    }
+}
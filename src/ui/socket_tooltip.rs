@@ -0,0 +1,214 @@
+//! A tooltip that floats next to whichever socket is hovered, saying what it's been up to.
+
+use bevy::{
+    color::palettes::{
+        css::{ORANGE, WHITE},
+        tailwind::GRAY_900,
+    },
+    prelude::*,
+    sprite::Anchor,
+    text::{Text2dBounds, TextLayoutInfo},
+};
+use bevy_mod_picking::picking_core::Pickable;
+
+use crate::{
+    game::{
+        assets::{FontKey, HandleMap},
+        number_format::format_number,
+        settings::Settings,
+        spawn::level::{Ring, Socket, SocketColor},
+    },
+    screen::Screen,
+};
+
+use super::{hotbar::map_socket_color_description_text, shop::UpgradeHistory};
+
+const TOOLTIP_WIDTH: f32 = 220.;
+const TOOLTIP_PADDING: f32 = 8.;
+/// How far above the socket's center the tooltip sits.
+const TOOLTIP_OFFSET: f32 = 40.;
+
+pub(super) fn plugin(app: &mut App) {
+    app.observe(show_socket_tooltip);
+    app.observe(hide_socket_tooltip);
+
+    app.add_systems(
+        Update,
+        (update_socket_tooltip, fit_socket_tooltip_background)
+            .chain()
+            .run_if(any_with_component::<SocketTooltip>),
+    );
+}
+
+/// Triggered when the pointer moves onto a socket.
+#[derive(Event)]
+pub struct ShowSocketTooltip {
+    pub socket: Entity,
+}
+
+/// Triggered when the pointer leaves a socket.
+#[derive(Event)]
+pub struct HideSocketTooltip {
+    pub socket: Entity,
+}
+
+#[derive(Component)]
+struct SocketTooltip {
+    socket: Entity,
+}
+
+#[derive(Component)]
+struct SocketTooltipText;
+
+fn show_socket_tooltip(
+    trigger: Trigger<ShowSocketTooltip>,
+    mut commands: Commands,
+    q_tooltip: Query<Entity, With<SocketTooltip>>,
+    screen: Res<State<Screen>>,
+    font_handles: Res<HandleMap<FontKey>>,
+) {
+    // the title screen's ring is just for show
+    if *screen.get() != Screen::Playing {
+        return;
+    }
+
+    for tooltip in &q_tooltip {
+        commands.entity(tooltip).despawn_recursive();
+    }
+
+    commands
+        .spawn((
+            Name::new("Socket Tooltip"),
+            SpriteBundle {
+                sprite: Sprite {
+                    color: GRAY_900.with_alpha(0.9).into(),
+                    anchor: Anchor::BottomCenter,
+                    custom_size: Some(Vec2::ZERO),
+                    ..default()
+                },
+                ..default()
+            },
+            SocketTooltip {
+                socket: trigger.event().socket,
+            },
+            // it mustn't steal the hover from the socket underneath
+            Pickable::IGNORE,
+            StateScoped(Screen::Playing),
+        ))
+        .with_children(|tooltip| {
+            let font = font_handles[&FontKey::Default].clone();
+            let style = |color: Srgba| TextStyle {
+                font: font.clone(),
+                font_size: 14.,
+                color: color.into(),
+            };
+
+            tooltip.spawn((
+                Text2dBundle {
+                    // filled in by `update_socket_tooltip`
+                    text: Text::from_sections([
+                        TextSection::new("", style(ORANGE)),
+                        TextSection::new("", style(WHITE)),
+                    ]),
+                    text_anchor: Anchor::BottomCenter,
+                    text_2d_bounds: Text2dBounds {
+                        size: Vec2::new(TOOLTIP_WIDTH, f32::INFINITY),
+                    },
+                    transform: Transform::from_xyz(0., TOOLTIP_PADDING, 1.),
+                    ..default()
+                },
+                SocketTooltipText,
+            ));
+        });
+}
+
+fn hide_socket_tooltip(
+    trigger: Trigger<HideSocketTooltip>,
+    mut commands: Commands,
+    q_tooltip: Query<(Entity, &SocketTooltip)>,
+) {
+    for (entity, tooltip) in &q_tooltip {
+        if tooltip.socket == trigger.event().socket {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// The cooldown ticks down while the tooltip is open, so it's rewritten every frame.
+fn update_socket_tooltip(
+    mut commands: Commands,
+    mut q_tooltip: Query<(Entity, &SocketTooltip, &mut Transform, &Children)>,
+    mut q_text: Query<&mut Text, With<SocketTooltipText>>,
+    q_socket: Query<(&Socket, &GlobalTransform)>,
+    q_ring: Query<&Ring>,
+    upgrade_history: Res<UpgradeHistory>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+) {
+    for (entity, tooltip, mut transform, children) in &mut q_tooltip {
+        let Ok((socket, socket_transform)) = q_socket.get(tooltip.socket) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+
+        transform.translation =
+            (socket_transform.translation().xy() + Vec2::Y * TOOLTIP_OFFSET).extend(200.);
+
+        let Some(text_entity) = children.iter().find(|child| q_text.contains(**child)) else {
+            continue;
+        };
+        let mut text = q_text.get_mut(*text_entity).unwrap();
+
+        if socket.color == SocketColor::NONE {
+            text.sections[0].value = String::from("Empty socket");
+            text.sections[1].value =
+                String::from("\nClick to slot in the selected orb. Right click to take one out.");
+            continue;
+        }
+
+        let cooldown_remaining = (socket.last_triggered_time_seconds
+            + socket.trigger_duration_seconds
+            - time.elapsed_seconds())
+        .max(0.);
+
+        let triggers_this_cycle = q_ring.get(socket.ring).map_or(0, |ring| {
+            ring.cycle
+                .iter()
+                .filter(|trigger| trigger.socket_index == socket.index)
+                .count()
+        });
+
+        text.sections[0].value = format!("{} socket", socket.color.as_str());
+        text.sections[1].value = format!(
+            "\nCooldown: {}\nTriggers this cycle: {}\nEarned last cycle: ${}\n{}",
+            if cooldown_remaining > 0. {
+                format!("{:.1}s", cooldown_remaining)
+            } else {
+                String::from("ready")
+            },
+            triggers_this_cycle,
+            format_number(&socket.earned_last_cycle, settings.number_format),
+            map_socket_color_description_text(socket.color, &upgrade_history),
+        );
+    }
+}
+
+/// Sprites don't size themselves to their text, so the background is stretched to fit once the text is laid out.
+fn fit_socket_tooltip_background(
+    mut q_tooltip: Query<(&mut Sprite, &Children), With<SocketTooltip>>,
+    q_text_layout: Query<&TextLayoutInfo, With<SocketTooltipText>>,
+) {
+    for (mut sprite, children) in &mut q_tooltip {
+        let Some(layout) = children
+            .iter()
+            .find_map(|child| q_text_layout.get(*child).ok())
+        else {
+            continue;
+        };
+
+        let size = layout.logical_size + Vec2::splat(TOOLTIP_PADDING * 2.);
+        if sprite.custom_size != Some(size) {
+            sprite.custom_size = Some(size);
+        }
+    }
+}
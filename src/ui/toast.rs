@@ -0,0 +1,240 @@
+//! Short notifications that stack at the top of the screen and fade away on their own.
+//!
+//! Only a few are shown at once. The rest wait in a queue, where more important toasts
+//! cut ahead of less important ones.
+
+use std::collections::VecDeque;
+
+use bevy::{
+    color::palettes::{
+        css::{ORANGE, WHITE},
+        tailwind::{CYAN_400, GRAY_400, GRAY_800, GRAY_900},
+    },
+    prelude::*,
+    ui::Val::*,
+};
+use bevy_mod_picking::picking_core::Pickable;
+
+use crate::game::assets::{FontKey, HandleMap};
+
+use super::event_log::LogEvent;
+
+const MAX_VISIBLE_TOASTS: usize = 3;
+const TOAST_FADE_SECONDS: f32 = 0.5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ToastQueue>();
+
+    app.observe(queue_toast);
+
+    app.add_systems(Update, (expire_toasts, show_queued_toasts, fade_toasts).chain());
+}
+
+/// How much a toast matters. Higher priorities are shown first and stay up longer.
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum ToastPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl ToastPriority {
+    fn seconds(&self) -> f32 {
+        match self {
+            ToastPriority::Low => 2.5,
+            ToastPriority::Normal => 4.,
+            ToastPriority::High => 6.,
+        }
+    }
+
+    fn border_color(&self) -> Color {
+        match self {
+            ToastPriority::Low => GRAY_900.into(),
+            ToastPriority::Normal => GRAY_400.into(),
+            ToastPriority::High => CYAN_400.into(),
+        }
+    }
+}
+
+/// Trigger this event to show a toast on whatever screen is open. It's also written to the event log.
+#[derive(Event, Clone, Default)]
+pub struct ShowToast {
+    pub title: String,
+    pub body: String,
+    pub priority: ToastPriority,
+    /// The ring this is about, if any, so the event log can take the camera there.
+    pub ring: Option<Entity>,
+}
+
+/// Toasts waiting for a free spot on screen, most important first.
+#[derive(Resource, Default)]
+pub struct ToastQueue(VecDeque<ShowToast>);
+
+#[derive(Component)]
+struct ToastContainer;
+
+#[derive(Component)]
+struct Toast {
+    priority: ToastPriority,
+    expires_at_seconds: f32,
+}
+
+fn queue_toast(trigger: Trigger<ShowToast>, mut commands: Commands, mut queue: ResMut<ToastQueue>) {
+    let toast = trigger.event().clone();
+
+    commands.trigger(LogEvent {
+        text: format!("{}: {}", toast.title, toast.body),
+        ring: toast.ring,
+    });
+
+    // behind everything at least as important, so equal toasts keep their order
+    let index = queue
+        .0
+        .iter()
+        .position(|queued| queued.priority < toast.priority)
+        .unwrap_or(queue.0.len());
+    queue.0.insert(index, toast);
+}
+
+fn show_queued_toasts(
+    mut commands: Commands,
+    mut queue: ResMut<ToastQueue>,
+    q_container: Query<Entity, With<ToastContainer>>,
+    mut q_toast: Query<&mut Toast>,
+    font_handles: Res<HandleMap<FontKey>>,
+    time: Res<Time<Real>>,
+) {
+    let Some(next) = queue.0.front() else {
+        return;
+    };
+
+    let now = time.elapsed_seconds();
+    let showing = q_toast
+        .iter()
+        .filter(|toast| toast.expires_at_seconds > now + TOAST_FADE_SECONDS)
+        .count();
+
+    if showing >= MAX_VISIBLE_TOASTS {
+        // make room by fading out the oldest toast that matters less than the one waiting
+        if let Some(mut toast) = q_toast
+            .iter_mut()
+            .filter(|toast| toast.priority < next.priority)
+            .find(|toast| toast.expires_at_seconds > now + TOAST_FADE_SECONDS)
+        {
+            toast.expires_at_seconds = now + TOAST_FADE_SECONDS;
+        }
+        return;
+    }
+
+    let toast = queue.0.pop_front().unwrap();
+
+    let container = match q_container.get_single() {
+        Ok(container) => container,
+        Err(_) => commands
+            .spawn((
+                Name::new("Toasts"),
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        top: Px(8.),
+                        width: Percent(100.),
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Px(4.),
+                        ..default()
+                    },
+                    z_index: ZIndex::Global(200),
+                    ..default()
+                },
+                Pickable::IGNORE,
+                ToastContainer,
+            ))
+            .id(),
+    };
+
+    let font = font_handles[&FontKey::Default].clone();
+
+    commands.entity(container).with_children(|container| {
+        container
+            .spawn((
+                Name::new("Toast"),
+                NodeBundle {
+                    style: Style {
+                        width: Px(260.),
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Column,
+                        padding: UiRect::all(Px(8.)),
+                        border: UiRect::all(Px(2.)),
+                        ..default()
+                    },
+                    background_color: GRAY_800.into(),
+                    border_color: toast.priority.border_color().into(),
+                    ..default()
+                },
+                Pickable::IGNORE,
+                Toast {
+                    priority: toast.priority,
+                    expires_at_seconds: now + toast.priority.seconds(),
+                },
+            ))
+            .with_children(|toast_node| {
+                toast_node.spawn(TextBundle::from_section(
+                    toast.title,
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 18.,
+                        color: ORANGE.into(),
+                    },
+                ));
+                toast_node.spawn(TextBundle::from_section(
+                    toast.body,
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 14.,
+                        color: WHITE.into(),
+                    },
+                ));
+            });
+    });
+}
+
+fn fade_toasts(
+    mut q_toast: Query<(&Toast, &mut BackgroundColor, &mut BorderColor, &Children)>,
+    mut q_text: Query<&mut Text>,
+    time: Res<Time<Real>>,
+) {
+    for (toast, mut background_color, mut border_color, children) in &mut q_toast {
+        let alpha = ((toast.expires_at_seconds - time.elapsed_seconds()) / TOAST_FADE_SECONDS)
+            .clamp(0., 1.);
+
+        if background_color.0.alpha() == alpha {
+            continue;
+        }
+
+        background_color.0.set_alpha(alpha);
+        border_color.0.set_alpha(alpha);
+
+        for child in children {
+            if let Ok(mut text) = q_text.get_mut(*child) {
+                for section in &mut text.sections {
+                    section.style.color.set_alpha(alpha);
+                }
+            }
+        }
+    }
+}
+
+fn expire_toasts(
+    mut commands: Commands,
+    q_toast: Query<(Entity, &Toast)>,
+    // real time, so toasts still go away while the game is paused
+    time: Res<Time<Real>>,
+) {
+    for (entity, toast) in &q_toast {
+        if time.elapsed_seconds() >= toast.expires_at_seconds {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -11,53 +11,79 @@ use bevy::{
     ecs::system::EntityCommands,
     math::VectorSpace,
     prelude::*,
-    ui::Val::*,
+    ui::{FocusPolicy, RelativeCursorPosition, Val::*},
 };
 use bevy_mod_picking::{events::{Click, Pointer}, picking_core::Pickable, prelude::On};
 use num_bigint::BigUint;
 
-use crate::{game::{
+use crate::game::{
     materials::materials::{SocketMaterial, SocketUiMaterial},
+    number_format::format_number,
+    settings::NumberFormat,
     spawn::level::SocketColor,
-}, screen::playing::format_scientific};
+};
 
-use super::{interaction::InteractionPalette, palette::*, shop::UpgradeButtonsContainer};
+use super::{
+    achievements::OpenAchievements,
+    event_log::ToggleEventLog,
+    interaction::InteractionPalette,
+    palette::*,
+    settings::OpenSettings,
+    shop::{
+        CycleShopSort, ShopControl, ShopScrollArea, ShopSection, ShopSectionButtons,
+        ShopSectionNode, ToggleHideUnaffordable, UpgradeButtonsContainer,
+    },
+};
 
 /// An extension trait for spawning UI widgets.
 pub trait Widgets {
     /// Spawn a simple button with text.
-    fn button(&mut self, text: impl Into<String>, font: Handle<Font>) -> EntityCommands;
+    fn button(&mut self, text: impl Into<String>, font: Handle<Font>) -> EntityCommands<'_>;
 
     /// Spawn a simple header label. Bigger than [`Widgets::label`].
-    fn header(&mut self, text: impl Into<String>) -> EntityCommands;
+    fn header(&mut self, text: impl Into<String>) -> EntityCommands<'_>;
 
     /// Spawn a simple text label.
-    fn label(&mut self, text: impl Into<String>) -> EntityCommands;
+    fn label(&mut self, text: impl Into<String>) -> EntityCommands<'_>;
 
-    fn horizontal_container(&mut self, justify_content: JustifyContent, align_items: AlignItems) -> EntityCommands;
+    fn horizontal_container(&mut self, justify_content: JustifyContent, align_items: AlignItems) -> EntityCommands<'_>;
 
-    fn vertical_container(&mut self, justify_content: JustifyContent, gap: Val) -> EntityCommands;
+    fn vertical_container(&mut self, justify_content: JustifyContent, gap: Val) -> EntityCommands<'_>;
+
+    fn upgrade_shop(&mut self, font: Handle<Font>) -> EntityCommands<'_>;
+    /// Spawn a titled group of shop buttons, hidden while it has nothing in it.
+    fn shop_section(&mut self, section: ShopSection, font: Handle<Font>) -> EntityCommands<'_>;
+    /// Spawn a small button for sorting or filtering the shop. Its label is filled in by the shop.
+    fn shop_control_button(&mut self, font: Handle<Font>) -> EntityCommands<'_>;
+    fn audio_button(&mut self, font: Handle<Font>) -> EntityCommands<'_>;
+    /// Spawn a small button in the corner of the score display that triggers `event` when clicked.
+    fn menu_button(
+        &mut self,
+        label: impl Into<String>,
+        font: Handle<Font>,
+        event: impl Event + Clone,
+    ) -> EntityCommands<'_>;
 
-    fn upgrade_shop(&mut self, font: Handle<Font>) -> EntityCommands;
-    fn audio_button(&mut self, font: Handle<Font>) -> EntityCommands;
+    /// Spawn a horizontal slider. Its [`Slider::value`] follows the cursor while pressed.
+    fn slider(&mut self, value: f32) -> EntityCommands<'_>;
     fn shop_button(
         &mut self,
         price: &BigUint,
         description: impl Into<String>,
         font: Handle<Font>,
-    ) -> EntityCommands;
+    ) -> EntityCommands<'_>;
 
     fn scoreboard_cycles_text(
         &mut self,
         font: Handle<Font>,
-    ) -> EntityCommands;
+    ) -> EntityCommands<'_>;
 
     fn scoreboard_currency_text(
         &mut self,
         font: Handle<Font>,
-    ) -> EntityCommands;
+    ) -> EntityCommands<'_>;
 
-    fn score_display(&mut self, font: Handle<Font>) -> EntityCommands;
+    fn score_display(&mut self, font: Handle<Font>) -> EntityCommands<'_>;
 
     fn hotbar_description(
         &mut self,
@@ -65,25 +91,25 @@ pub trait Widgets {
         socket_color: SocketColor,
         font: Handle<Font>,
         socket_material: Handle<SocketUiMaterial>,
-    ) -> EntityCommands;
+    ) -> EntityCommands<'_>;
 
-    fn hotbar(&mut self, starting_colors: Vec<SocketColor>) -> EntityCommands;
+    fn hotbar(&mut self, starting_colors: Vec<SocketColor>) -> EntityCommands<'_>;
 
     fn hotbar_button(
         &mut self,
         socket_material: Handle<SocketUiMaterial>,
         hotkey_text: impl Into<String>,
         index: u32,
-    ) -> EntityCommands;
+    ) -> EntityCommands<'_>;
 
-    fn socket(&mut self, socket_material: Handle<SocketUiMaterial>) -> EntityCommands;
+    fn socket(&mut self, socket_material: Handle<SocketUiMaterial>) -> EntityCommands<'_>;
 }
 
 #[derive(Event)]
 pub struct ToggleAudio;
 
 impl<T: Spawn> Widgets for T {
-    fn button(&mut self, text: impl Into<String>, font: Handle<Font>) -> EntityCommands {
+    fn button(&mut self, text: impl Into<String>, font: Handle<Font>) -> EntityCommands<'_> {
         let mut entity = self.spawn((
             Name::new("Button"),
             ButtonBundle {
@@ -122,7 +148,7 @@ impl<T: Spawn> Widgets for T {
         entity
     }
 
-    fn audio_button(&mut self, font: Handle<Font>) -> EntityCommands {
+    fn audio_button(&mut self, font: Handle<Font>) -> EntityCommands<'_> {
         let mut entity = self.spawn((
             Name::new("Button"),
             ButtonBundle {
@@ -165,12 +191,96 @@ impl<T: Spawn> Widgets for T {
         entity
     }
 
+    fn menu_button(
+        &mut self,
+        label: impl Into<String>,
+        font: Handle<Font>,
+        event: impl Event + Clone,
+    ) -> EntityCommands<'_> {
+        let mut entity = self.spawn((
+            Name::new("Button"),
+            ButtonBundle {
+                style: Style {
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    align_self: AlignSelf::End,
+                    border: UiRect::all(Px(1.)),
+                    margin: UiRect::all(Px(4.)),
+                    padding: UiRect::all(Px(4.)),
+                    ..default()
+                },
+                background_color: GRAY_700.into(),
+                border_color: GRAY_400.into(),
+                ..default()
+            },
+            InteractionPalette {
+                none: GRAY_700.into(),
+                hovered: GRAY_600.into(),
+                pressed: GRAY_500.into(),
+            },
+            On::<Pointer<Click>>::commands_mut(move |_, c| {
+                c.trigger(event.clone())
+            })
+        ));
+        entity.with_children(|children| {
+            children.spawn((
+                Name::new("Button Text"),
+                TextBundle::from_section(
+                    label,
+                    TextStyle {
+                        font_size: 14.0,
+                        font,
+                        color: BUTTON_TEXT,
+                    },
+                ),
+            ));
+        });
+        entity
+    }
+
+    fn slider(&mut self, value: f32) -> EntityCommands<'_> {
+        let mut entity = self.spawn((
+            Name::new("Slider"),
+            ButtonBundle {
+                style: Style {
+                    width: Px(150.0),
+                    height: Px(20.0),
+                    border: UiRect::all(Px(1.)),
+                    ..default()
+                },
+                background_color: GRAY_900.into(),
+                border_color: GRAY_400.into(),
+                ..default()
+            },
+            RelativeCursorPosition::default(),
+            Slider { value },
+        ));
+        entity.with_children(|children| {
+            children.spawn((
+                Name::new("Slider Fill"),
+                NodeBundle {
+                    style: Style {
+                        width: Percent(value * 100.),
+                        height: Percent(100.),
+                        ..default()
+                    },
+                    background_color: GRAY_500.into(),
+                    ..default()
+                },
+                SliderFill,
+                Pickable::IGNORE,
+            ));
+        });
+        entity
+    }
+
     fn shop_button(
         &mut self,
         price: &BigUint,
         description: impl Into<String>,
         font: Handle<Font>,
-    ) -> EntityCommands {
+    ) -> EntityCommands<'_> {
+        let description = description.into();
         let mut entity = self.spawn((
             Name::new("Button"),
             ButtonBundle {
@@ -184,6 +294,8 @@ impl<T: Spawn> Widgets for T {
                 },
                 background_color: GRAY_700.into(),
                 border_color: GRAY_400.into(),
+                // so the shop's scroll area underneath still counts as hovered
+                focus_policy: FocusPolicy::Pass,
                 ..default()
             },
             InteractionPalette {
@@ -193,6 +305,7 @@ impl<T: Spawn> Widgets for T {
             },
             ShopButton {
                 price: price.clone(),
+                description: description.clone(),
             },
         ));
 
@@ -204,7 +317,8 @@ impl<T: Spawn> Widgets for T {
                         .spawn((
                             Name::new("Button Price Text"),
                             TextBundle::from_section(
-                                format!("${}", format_scientific(&price)),
+                                // replaced with the player's chosen format as soon as the shop sees it
+                                format!("${}", format_number(&price, NumberFormat::default())),
                                 TextStyle {
                                     font_size: 14.0,
                                     color: ORANGE.into(),
@@ -212,6 +326,9 @@ impl<T: Spawn> Widgets for T {
                                     ..default()
                                 },
                             ),
+                            ShopButtonPriceText {
+                                price: price.clone(),
+                            },
                         ))
                         .insert(Style {
                             margin: UiRect::all(Px(8.)),
@@ -240,7 +357,7 @@ impl<T: Spawn> Widgets for T {
         entity
     }
 
-    fn header(&mut self, text: impl Into<String>) -> EntityCommands {
+    fn header(&mut self, text: impl Into<String>) -> EntityCommands<'_> {
         let mut entity = self.spawn((
             Name::new("Header"),
             NodeBundle {
@@ -274,7 +391,7 @@ impl<T: Spawn> Widgets for T {
     fn scoreboard_cycles_text(
         &mut self,
         font: Handle<Font>,
-    ) -> EntityCommands {
+    ) -> EntityCommands<'_> {
         let mut entity = self.spawn((
             Name::new("Scoreboard Text"),
             NodeBundle {
@@ -323,7 +440,7 @@ impl<T: Spawn> Widgets for T {
     fn scoreboard_currency_text(
         &mut self,
         font: Handle<Font>,
-    ) -> EntityCommands {
+    ) -> EntityCommands<'_> {
         let mut entity = self.spawn((
             Name::new("Scoreboard Text"),
             NodeBundle {
@@ -396,7 +513,7 @@ impl<T: Spawn> Widgets for T {
         entity
     }
 
-    fn label(&mut self, text: impl Into<String>) -> EntityCommands {
+    fn label(&mut self, text: impl Into<String>) -> EntityCommands<'_> {
         let mut entity = self.spawn((
             Name::new("Label"),
             NodeBundle {
@@ -425,7 +542,7 @@ impl<T: Spawn> Widgets for T {
         entity
     }
 
-    fn horizontal_container(&mut self, justify_content: JustifyContent, align_items: AlignItems) -> EntityCommands {
+    fn horizontal_container(&mut self, justify_content: JustifyContent, align_items: AlignItems) -> EntityCommands<'_> {
         let entity = self.spawn((
             Name::new("FlexWrapper"),
             NodeBundle {
@@ -450,7 +567,7 @@ impl<T: Spawn> Widgets for T {
         entity
     }
 
-    fn vertical_container(&mut self, justify_content: JustifyContent, gap: Val) -> EntityCommands {
+    fn vertical_container(&mut self, justify_content: JustifyContent, gap: Val) -> EntityCommands<'_> {
         let entity = self.spawn((
             Name::new("FlexWrapper"),
             NodeBundle {
@@ -474,7 +591,7 @@ impl<T: Spawn> Widgets for T {
         entity
     }
 
-    fn upgrade_shop(&mut self, font: Handle<Font>) -> EntityCommands {
+    fn upgrade_shop(&mut self, font: Handle<Font>) -> EntityCommands<'_> {
         let mut shop_entity = self.spawn((
             Name::new("UpgradeShop"),
             NodeBundle {
@@ -501,27 +618,177 @@ impl<T: Spawn> Widgets for T {
                         .spawn(TextBundle::from_section(
                             String::from("Upgrade Shop"),
                             TextStyle {
-                                font,
+                                font: font.clone(),
                                 font_size: 16.0,
                                 color: WHITE.into(),
-                                ..default()
                             },
                         ))
                         .insert(Style {
-                            padding: UiRect::bottom(Px(16.)),
+                            padding: UiRect::bottom(Px(8.)),
                             ..default()
                         });
 
                     shop_vertical_children
-                        .vertical_container(JustifyContent::Start, Px(8.))
-                        .insert(UpgradeButtonsContainer);
+                        .spawn((
+                            Name::new("Shop Controls"),
+                            NodeBundle {
+                                style: Style {
+                                    flex_wrap: FlexWrap::Wrap,
+                                    column_gap: Px(4.),
+                                    row_gap: Px(4.),
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                            Pickable::IGNORE,
+                        ))
+                        .with_children(|controls| {
+                            controls.shop_control_button(font.clone()).insert((
+                                ShopControl::Sort,
+                                On::<Pointer<Click>>::commands_mut(|_, c| c.trigger(CycleShopSort)),
+                            ));
+                            controls.shop_control_button(font.clone()).insert((
+                                ShopControl::HideUnaffordable,
+                                On::<Pointer<Click>>::commands_mut(|_, c| {
+                                    c.trigger(ToggleHideUnaffordable)
+                                }),
+                            ));
+                        });
+
+                    // the list is moved up and down inside this to scroll
+                    shop_vertical_children
+                        .spawn((
+                            Name::new("Shop Scroll Area"),
+                            NodeBundle {
+                                style: Style {
+                                    width: Percent(100.),
+                                    max_height: Vh(75.),
+                                    overflow: Overflow::clip_y(),
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                            // hovering is what lets the mouse wheel scroll the list instead of zooming
+                            Interaction::default(),
+                            ShopScrollArea,
+                        ))
+                        .with_children(|scroll_area| {
+                            scroll_area
+                                .spawn((
+                                    Name::new("Shop List"),
+                                    NodeBundle {
+                                        style: Style {
+                                            width: Percent(100.),
+                                            flex_direction: FlexDirection::Column,
+                                            row_gap: Px(8.),
+                                            ..default()
+                                        },
+                                        ..default()
+                                    },
+                                    Pickable::IGNORE,
+                                    UpgradeButtonsContainer::default(),
+                                ))
+                                .with_children(|list| {
+                                    for section in ShopSection::ALL {
+                                        list.shop_section(section, font.clone());
+                                    }
+                                });
+                        });
                 });
         });
 
         shop_entity
     }
 
-    fn score_display(&mut self, font: Handle<Font>) -> EntityCommands {
+    fn shop_section(&mut self, section: ShopSection, font: Handle<Font>) -> EntityCommands<'_> {
+        let mut entity = self.spawn((
+            Name::new("Shop Section"),
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Px(8.),
+                    // shown once something's for sale in it
+                    display: Display::None,
+                    ..default()
+                },
+                ..default()
+            },
+            Pickable::IGNORE,
+            ShopSectionNode(section),
+        ));
+
+        entity.with_children(|children| {
+            children.spawn((
+                Name::new("Shop Section Header"),
+                TextBundle::from_section(
+                    section.title(),
+                    TextStyle {
+                        font,
+                        font_size: 14.0,
+                        color: GRAY_400.into(),
+                    },
+                ),
+                Pickable::IGNORE,
+            ));
+
+            children.spawn((
+                Name::new("Shop Section Buttons"),
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Px(8.),
+                        ..default()
+                    },
+                    ..default()
+                },
+                Pickable::IGNORE,
+                ShopSectionButtons(section),
+            ));
+        });
+
+        entity
+    }
+
+    fn shop_control_button(&mut self, font: Handle<Font>) -> EntityCommands<'_> {
+        let mut entity = self.spawn((
+            Name::new("Button"),
+            ButtonBundle {
+                style: Style {
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    border: UiRect::all(Px(1.)),
+                    padding: UiRect::all(Px(4.)),
+                    ..default()
+                },
+                background_color: GRAY_700.into(),
+                border_color: GRAY_400.into(),
+                ..default()
+            },
+            InteractionPalette {
+                none: GRAY_700.into(),
+                hovered: GRAY_600.into(),
+                pressed: GRAY_500.into(),
+            },
+        ));
+        entity.with_children(|children| {
+            children.spawn((
+                Name::new("Button Text"),
+                // filled in by the shop once it knows how it's being sorted and filtered
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 12.0,
+                        font,
+                        color: BUTTON_TEXT,
+                    },
+                ),
+                Pickable::IGNORE,
+            ));
+        });
+        entity
+    }
+
+    fn score_display(&mut self, font: Handle<Font>) -> EntityCommands<'_> {
         let mut entity = self.spawn((
             Name::new("ScoreDisplay"),
             NodeBundle {
@@ -543,6 +810,9 @@ impl<T: Spawn> Widgets for T {
                 .vertical_container(JustifyContent::Start, Px(0.))
                 .with_children(|score_display_container| {
                     score_display_container.audio_button(font.clone());
+                    score_display_container.menu_button("Settings", font.clone(), OpenSettings);
+                    score_display_container.menu_button("Achievements", font.clone(), OpenAchievements);
+                    score_display_container.menu_button("Event Log", font.clone(), ToggleEventLog);
                     score_display_container
                         .scoreboard_cycles_text(font.clone());
                     score_display_container
@@ -553,7 +823,7 @@ impl<T: Spawn> Widgets for T {
         entity
     }
 
-    fn hotbar(&mut self, starting_colors: Vec<SocketColor>) -> EntityCommands {
+    fn hotbar(&mut self, starting_colors: Vec<SocketColor>) -> EntityCommands<'_> {
         let entity = self.spawn((
             Name::new("Hotbar"),
             NodeBundle {
@@ -583,7 +853,7 @@ impl<T: Spawn> Widgets for T {
         socket_color: SocketColor,
         font: Handle<Font>,
         socket_material: Handle<SocketUiMaterial>,
-    ) -> EntityCommands {
+    ) -> EntityCommands<'_> {
         let mut entity = self.spawn((
             Name::new("HotbarDescription"),
             NodeBundle {
@@ -647,7 +917,7 @@ impl<T: Spawn> Widgets for T {
         socket_material: Handle<SocketUiMaterial>,
         hotkey_text: impl Into<String>,
         index: u32,
-    ) -> EntityCommands {
+    ) -> EntityCommands<'_> {
         let mut entity = self.spawn((
             Name::new("HotbarButton"),
             NodeBundle {
@@ -711,7 +981,7 @@ impl<T: Spawn> Widgets for T {
         entity
     }
 
-    fn socket(&mut self, socket_material: Handle<SocketUiMaterial>) -> EntityCommands {
+    fn socket(&mut self, socket_material: Handle<SocketUiMaterial>) -> EntityCommands<'_> {
         let entity = self.spawn(MaterialNodeBundle {
             style: Style {
                 width: Px(64.),
@@ -768,8 +1038,24 @@ pub struct UpgradeShop;
 #[derive(Component)]
 pub struct ShopButton {
     pub price: BigUint,
+    pub description: String,
+}
+
+/// The price label of a [`ShopButton`], reformatted when the number format setting changes.
+#[derive(Component)]
+pub struct ShopButtonPriceText {
+    pub price: BigUint,
 }
 
+/// A draggable value between 0 and 1.
+#[derive(Component)]
+pub struct Slider {
+    pub value: f32,
+}
+
+#[derive(Component)]
+pub struct SliderFill;
+
 #[derive(Event)]
 pub struct HotbarChanged {
     pub index: u32,
@@ -779,11 +1065,11 @@ pub struct HotbarChanged {
 pub trait Containers {
     /// Spawns a root node that covers the full screen
     /// and centers its content horizontally and vertically.
-    fn ui_root(&mut self) -> EntityCommands;
+    fn ui_root(&mut self) -> EntityCommands<'_>;
 }
 
 impl Containers for Commands<'_, '_> {
-    fn ui_root(&mut self) -> EntityCommands {
+    fn ui_root(&mut self) -> EntityCommands<'_> {
         self.spawn((
             Name::new("UI Root"),
             NodeBundle {
@@ -812,17 +1098,17 @@ impl Containers for Commands<'_, '_> {
 /// are able to spawn entities.
 /// Ideally, this trait should be [part of Bevy itself](https://github.com/bevyengine/bevy/issues/14231).
 trait Spawn {
-    fn spawn<B: Bundle>(&mut self, bundle: B) -> EntityCommands;
+    fn spawn<B: Bundle>(&mut self, bundle: B) -> EntityCommands<'_>;
 }
 
 impl Spawn for Commands<'_, '_> {
-    fn spawn<B: Bundle>(&mut self, bundle: B) -> EntityCommands {
+    fn spawn<B: Bundle>(&mut self, bundle: B) -> EntityCommands<'_> {
         self.spawn(bundle)
     }
 }
 
 impl Spawn for ChildBuilder<'_> {
-    fn spawn<B: Bundle>(&mut self, bundle: B) -> EntityCommands {
+    fn spawn<B: Bundle>(&mut self, bundle: B) -> EntityCommands<'_> {
         self.spawn(bundle)
     }
 }